@@ -0,0 +1,159 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+use serde::{Deserialize, Serialize};
+
+/// The kind of tag for an NPC's species.
+pub const SPECIES: &str = "species";
+/// The kind of tag for an NPC's culture.
+pub const CULTURE: &str = "culture";
+/// The kind of tag for an NPC's sex.
+pub const SEX: &str = "sex";
+/// The kind of tag for an NPC's faction.
+pub const FACTION: &str = "faction";
+
+/// Describes one thing about an NPC that reaction modifiers might
+/// care about, such as their species or culture.  
+/// Written as "kind=value", like "species=elf". A tag with no kind
+/// in a condition matches any tag with the same value.
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub struct AudienceTag {
+	pub kind: String,
+	pub value: String,
+}//end struct AudienceTag
+
+impl AudienceTag {
+	/// Creates a new tag of the given kind and value.
+	pub fn new(kind: &str, value: &str) -> AudienceTag {
+		AudienceTag {
+			kind: kind.trim().to_string(),
+			value: value.trim().to_string(),
+		}//end struct construction
+	}//end new()
+
+	/// Checks whether this tag, as part of a condition, matches
+	/// the other tag on an NPC. Case is ignored, and an empty kind
+	/// matches any kind.
+	pub fn matches(&self, other: &AudienceTag) -> bool {
+		(self.kind.is_empty() || self.kind.eq_ignore_ascii_case(&other.kind))
+			&& self.value.eq_ignore_ascii_case(&other.value)
+	}//end matches()
+}//end impl for AudienceTag
+
+impl FromStr for AudienceTag {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.split_once(['=', ':']) {
+			Some((kind, value)) => Ok(AudienceTag::new(kind, value)),
+			None => Ok(AudienceTag::new("", s)),
+		}//end matching whether there's a kind
+	}//end from_str()
+}//end impl FromStr for AudienceTag
+
+impl fmt::Display for AudienceTag {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.kind.is_empty() {write!(f, "{}", self.value)}
+		else {write!(f, "{}={}", self.kind, self.value)}
+	}//end fmt()
+}//end impl Display for AudienceTag
+
+/// A limit on who a reaction modifier applies to.  
+/// Written as a tag for Only, or a tag starting with "!" for Except.
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Serialize,Deserialize)]
+pub enum AudienceCondition {
+	/// The NPC must have a matching tag.
+	Only(AudienceTag),
+	/// The NPC must not have a matching tag.
+	Except(AudienceTag),
+}//end enum AudienceCondition
+
+impl FromStr for AudienceCondition {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		match s.strip_prefix('!') {
+			Some(rest) => Ok(AudienceCondition::Except(rest.parse()?)),
+			None => Ok(AudienceCondition::Only(s.parse()?)),
+		}//end matching whether the condition is negated
+	}//end from_str()
+}//end impl FromStr for AudienceCondition
+
+impl fmt::Display for AudienceCondition {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			AudienceCondition::Only(tag) => write!(f, "{}", tag),
+			AudienceCondition::Except(tag) => write!(f, "!{}", tag),
+		}//end matching kind of condition
+	}//end fmt()
+}//end impl Display for AudienceCondition
+
+/// Checks whether an NPC with the given tags is in the audience
+/// described by the conditions.  
+/// Only conditions of the same kind are alternatives, so
+/// "species=elf, species=dwarf" means elves or dwarves, while
+/// "species=elf, sex=female" means female elves. Any matching
+/// Except condition rules the NPC out. No conditions means everyone.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::audience::{in_audience, parse_conditions, parse_tags};
+/// let elf_woman = parse_tags("species=elf, sex=female, culture=Sylvan");
+/// let dwarf = parse_tags("species=dwarf, sex=male");
+///
+/// let elves_or_dwarves = parse_conditions("species=elf, species=dwarf");
+/// assert!(in_audience(&elves_or_dwarves, &elf_woman));
+/// assert!(in_audience(&elves_or_dwarves, &dwarf));
+///
+/// let female_elves = parse_conditions("species=elf, sex=female");
+/// assert!(in_audience(&female_elves, &elf_woman));
+/// assert!(!in_audience(&female_elves, &dwarf));
+///
+/// let not_sylvan = parse_conditions("!sylvan");
+/// assert!(!in_audience(&not_sylvan, &elf_woman));
+/// assert!(in_audience(&not_sylvan, &dwarf));
+/// assert!(in_audience(&[], &dwarf));
+/// ```
+pub fn in_audience(conditions: &[AudienceCondition], tags: &[AudienceTag]) -> bool {
+	let mut required_kinds: Vec<String> = Vec::new();
+	for condition in conditions {
+		match condition {
+			AudienceCondition::Except(tag) => {
+				if tags.iter().any(|other| tag.matches(other)) { return false; }
+			},
+			AudienceCondition::Only(tag) => {
+				let kind = tag.kind.to_ascii_lowercase();
+				if !required_kinds.contains(&kind) { required_kinds.push(kind); }
+			},
+		}//end matching kind of condition
+	}//end checking each condition
+	// each kind of Only condition needs at least one match
+	required_kinds.iter().all(|kind| {
+		conditions.iter().any(|condition| match condition {
+			AudienceCondition::Only(tag) if tag.kind.eq_ignore_ascii_case(kind) => tags.iter().any(|other| tag.matches(other)),
+			_ => false,
+		})
+	})
+}//end in_audience()
+
+/// Reads a comma-separated list of tags, such as "species=elf, sex=female".
+pub fn parse_tags(text: &str) -> Vec<AudienceTag> {
+	text.split(',')
+		.filter(|part| !part.trim().is_empty())
+		.filter_map(|part| part.parse().ok())
+		.collect()
+}//end parse_tags()
+
+/// Reads a comma-separated list of conditions, such as "species=elf, !culture=Imperial".
+pub fn parse_conditions(text: &str) -> Vec<AudienceCondition> {
+	text.split(',')
+		.filter(|part| !part.trim().is_empty())
+		.filter_map(|part| part.parse().ok())
+		.collect()
+}//end parse_conditions()
+
+/// Writes a list of tags or conditions as comma-separated text,
+/// the same way that parse_tags() and parse_conditions() read them.
+pub fn join_list<T: fmt::Display>(items: &[T]) -> String {
+	items.iter().map(|item| item.to_string()).collect::<Vec<String>>().join(", ")
+}//end join_list()
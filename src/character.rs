@@ -1,5 +1,8 @@
+use std::{fmt, fs, io, path::Path};
 use serde::{Deserialize, Serialize};
 
+use crate::{audience::{self, AudienceCondition, AudienceTag}, reaction::{BreakdownEntry, EntryStatus, ReactionBreakdown, Situation, SkipReason}, traits::{self, TraitInfo}};
+
 
 /// Tracks one reaction modifier that might be attached to a character.
 #[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
@@ -7,6 +10,22 @@ pub struct ReactionMod {
 	pub name: String,
 	pub modi: i32,
 	pub enabled: bool,
+	/// Limits on which NPCs this modifier applies to.
+	/// If empty, then it applies to everyone.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub audience: Vec<AudienceCondition>,
+	/// If this modifier is a Reputation, how often it's recognized.  
+	/// The magnitude is modi, and the affected group is audience.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub reputation: Option<Recognition>,
+	/// The situations this modifier applies in.
+	/// If empty, then it applies in every situation.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub situations: Vec<Situation>,
+	/// If this modifier is a leveled trait, named by name, the level it's at.  
+	/// The value of modi then comes from the trait's table.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub level: Option<i32>,
 }//end struct RactionMod
 
 impl ReactionMod {
@@ -17,15 +36,168 @@ impl ReactionMod {
 			name: name.to_string(),
 			modi: modifier,
 			enabled: true,
+			audience: Vec::new(),
+			reputation: None,
+			situations: Vec::new(),
+			level: None,
 		}//end struct construction
 	}//end new()
+
+	/// Creates a new modifier for a leveled trait, taking its value
+	/// from the trait's table. The level is clamped to the table.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::ReactionMod;
+	/// # use gurps_reactions::traits::find_leveled_trait;
+	/// let habit = find_leveled_trait("Odious Personal Habit").unwrap();
+	/// let mut picks_nose = ReactionMod::leveled(&habit, 2);
+	/// assert_eq!(-2, picks_nose.modi);
+	/// assert!(picks_nose.set_level(3));
+	/// assert_eq!(-3, picks_nose.modi);
+	/// assert!(!ReactionMod::new("Kind", 1).set_level(2));
+	/// ```
+	pub fn leveled(leveled: &TraitInfo, level: i32) -> ReactionMod {
+		let level = leveled.clamp_level(level);
+		ReactionMod {
+			level: Some(level),
+			..ReactionMod::new(&leveled.name, leveled.modifier(level).unwrap_or(0))
+		}//end struct construction
+	}//end leveled()
+
+	/// Gets the leveled trait this modifier is for, if it's a leveled trait.
+	pub fn leveled_trait(&self) -> Option<&'static TraitInfo> {
+		self.level.and_then(|_| traits::find_leveled_trait(&self.name))
+	}//end leveled_trait()
+
+	/// Changes the level of a leveled trait, updating modi from the
+	/// trait's table. Returns false if this isn't a leveled trait.
+	pub fn set_level(&mut self, level: i32) -> bool {
+		match self.leveled_trait() {
+			Some(leveled) => {
+				let level = leveled.clamp_level(level);
+				self.level = Some(level);
+				self.modi = leveled.modifier(level).unwrap_or(0);
+				true
+			},
+			None => false,
+		}//end matching whether this is a leveled trait
+	}//end set_level()
+
+	/// Creates a new Reputation, which only applies to NPCs in the
+	/// affected group, and then only if they recognize the character.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::audience::parse_conditions;
+	/// # use gurps_reactions::character::{ReactionMod, Recognition};
+	/// let hero = ReactionMod::reputation("Hero of Megalos", 3, parse_conditions("culture=Megalos"), Recognition::TenOrLess);
+	/// assert_eq!(3, hero.modi);
+	/// assert_eq!(Some(Recognition::TenOrLess), hero.reputation);
+	/// assert_eq!(Some(10), Recognition::TenOrLess.target());
+	/// ```
+	pub fn reputation(name: &str, magnitude: i32, affected: Vec<AudienceCondition>, recognition: Recognition) -> ReactionMod {
+		ReactionMod {
+			audience: affected,
+			reputation: Some(recognition),
+			..ReactionMod::new(name, magnitude)
+		}//end struct construction
+	}//end reputation()
+
+	/// Checks whether this modifier applies to the NPC, based on
+	/// the audience conditions of the modifier and the tags of the NPC.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::audience::{parse_conditions, parse_tags};
+	/// # use gurps_reactions::character::{Npc, ReactionMod};
+	/// let mut reputation = ReactionMod::new("Elf-Friend", 2);
+	/// reputation.audience = parse_conditions("species=elf");
+	/// let mut elf = Npc::new("Elrond");
+	/// elf.tags = parse_tags("species=elf, culture=Rivendell");
+	/// assert!(reputation.applies_to(&elf));
+	/// assert!(!reputation.applies_to(&Npc::new("Bill Ferny")));
+	/// ```
+	pub fn applies_to(&self, npc: &Npc) -> bool {
+		audience::in_audience(&self.audience, &npc.tags)
+	}//end applies_to()
+
+	/// Checks whether this modifier applies in the situation.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::ReactionMod;
+	/// # use gurps_reactions::reaction::Situation;
+	/// let mut merchant_rank = ReactionMod::new("Merchant Rank", 2);
+	/// assert!(merchant_rank.applies_in(Situation::PotentialCombat));
+	/// merchant_rank.situations.push(Situation::Commercial);
+	/// assert!(merchant_rank.applies_in(Situation::Commercial));
+	/// assert!(!merchant_rank.applies_in(Situation::PotentialCombat));
+	/// ```
+	pub fn applies_in(&self, situation: Situation) -> bool {
+		self.situations.is_empty() || self.situations.contains(&situation)
+	}//end applies_in()
 }//end impl ReactionMod
 
+/// How often people in the affected group recognize
+/// someone with a Reputation.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub enum Recognition {
+	/// Everyone in the affected group recognizes them.
+	#[default]
+	Always,
+	/// Recognized on a roll of 10 or less.
+	TenOrLess,
+	/// Recognized on a roll of 7 or less.
+	SevenOrLess,
+}//end enum Recognition
+
+impl Recognition {
+	/// Every recognition frequency, from most to least often.
+	pub const ALL: [Recognition; 3] = [
+		Recognition::Always,
+		Recognition::TenOrLess,
+		Recognition::SevenOrLess,
+	];
+
+	/// The number to roll at or under on 3d6 to be recognized,
+	/// or None if no roll is needed.
+	pub fn target(&self) -> Option<i32> {
+		match self {
+			Recognition::Always => None,
+			Recognition::TenOrLess => Some(10),
+			Recognition::SevenOrLess => Some(7),
+		}//end matching self to target
+	}//end target()
+
+	/// The name to show for this recognition frequency.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Recognition::Always => "Always",
+			Recognition::TenOrLess => "10 or less",
+			Recognition::SevenOrLess => "7 or less",
+		}//end matching self to name
+	}//end name()
+}//end impl for Recognition
+
+impl fmt::Display for Recognition {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.name())
+	}//end fmt()
+}//end impl Display for Recognition
+
 /// A single character, which has a number of reaction modifiers.
 #[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
 pub struct Character {
 	pub name: String,
 	pub reaction_modifiers: Vec<ReactionMod>,
+	/// The character's Status, compared against the NPC's in an encounter.
+	#[serde(default)]
+	pub status: i32,
 }//end struct Character
 
 impl Character {
@@ -34,6 +206,7 @@ impl Character {
 		Character {
 			name: name.to_string(),
 			reaction_modifiers: Vec::new(),
+			status: 0,
 		}//end struct construction
 	}//end new()
 
@@ -59,7 +232,7 @@ impl Character {
 	/// # use gurps_reactions::character::Character;
 	/// let mut witch = Character::new("wicked witch");
 	/// witch.reaction_modifiers.push(ReactionMod::new("Wicked",-2));
-	/// let social_stigma = ReactionMod {name: "Stigma".to_string(), modi: -1, enabled: false};
+	/// let social_stigma = ReactionMod {name: "Stigma".to_string(), modi: -1, enabled: false, ..Default::default()};
 	/// witch.reaction_modifiers.push(social_stigma);
 	/// assert_eq!(-2, witch.reaction_sum(false));
 	/// assert_eq!(-3, witch.reaction_sum(true));
@@ -72,4 +245,542 @@ impl Character {
 				else {acum}
 			})
 	}//end reaction_sum()
-}//end impl for Character
\ No newline at end of file
+
+	/// Lists every reaction modifier on the character, along with
+	/// whether it counted towards the total, as with reaction_sum().
+	pub fn reaction_breakdown(&self, count_disabled: bool) -> ReactionBreakdown {
+		let entries = self.reaction_modifiers.iter()
+			.map(|modi| BreakdownEntry {
+				source: self.name.clone(),
+				modifier: modi.clone(),
+				status: if count_disabled || modi.enabled {EntryStatus::Counted}
+					else {EntryStatus::Skipped(SkipReason::Disabled)},
+				recognition: None,
+			})
+			.collect();
+		ReactionBreakdown {
+			entries,
+			total: self.reaction_sum(count_disabled),
+		}//end struct construction
+	}//end reaction_breakdown()
+}//end impl for Character
+
+/// A non-player character who is reacting to the party, with
+/// their own modifiers from things like Intolerance or Xenophilia.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Npc, ReactionMod};
+/// let mut guard = Npc::new("Gate Guard");
+/// guard.base_attitude = -1;
+/// guard.reaction_modifiers.push(ReactionMod::new("Intolerance (Elves)", -3));
+/// assert_eq!(-4, guard.reaction_sum(false));
+/// let breakdown = guard.reaction_breakdown(false);
+/// assert_eq!("Base Attitude", breakdown.entries[0].modifier.name);
+/// assert_eq!(-4, breakdown.total);
+/// ```
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub struct Npc {
+	pub name: String,
+	/// How the NPC feels about strangers before anything else is considered.
+	pub base_attitude: i32,
+	pub reaction_modifiers: Vec<ReactionMod>,
+	/// Things about the NPC that reaction modifiers with
+	/// audience conditions are checked against.
+	#[serde(default)]
+	pub tags: Vec<AudienceTag>,
+	/// The NPC's Status, compared against each character's in an encounter.
+	#[serde(default)]
+	pub status: i32,
+	/// Whether the NPC resents those of higher Status, so that
+	/// having a higher Status than them counts against you.
+	#[serde(default)]
+	pub resents_higher_status: bool,
+	/// The NPC's Will, which influence skills are rolled against.
+	/// If None, influence skills just need to succeed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub will: Option<i32>,
+}//end struct Npc
+
+impl Npc {
+	/// Creates a new NPC with a neutral attitude and no reaction modifiers.
+	pub fn new(name: &str) -> Npc {
+		Npc {
+			name: name.to_string(),
+			base_attitude: 0,
+			reaction_modifiers: Vec::new(),
+			tags: Vec::new(),
+			status: 0,
+			resents_higher_status: false,
+			will: None,
+		}//end struct construction
+	}//end new()
+
+	/// Gets the reaction modifier this NPC has towards someone of the given Status.  
+	/// If the NPC has the higher Status, the difference is a penalty.
+	/// If the NPC has the lower Status, the difference is a bonus,
+	/// unless the NPC resents those of higher Status, in which case
+	/// it's a penalty as well.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::Npc;
+	/// let mut baron = Npc::new("Baron");
+	/// baron.status = 3;
+	/// assert_eq!(-2, baron.status_reaction(1));
+	/// let mut peasant = Npc::new("Peasant");
+	/// peasant.status = -1;
+	/// assert_eq!(2, peasant.status_reaction(1));
+	/// peasant.resents_higher_status = true;
+	/// assert_eq!(-2, peasant.status_reaction(1));
+	/// assert_eq!(0, peasant.status_reaction(-1));
+	/// ```
+	pub fn status_reaction(&self, status: i32) -> i32 {
+		let difference = status - self.status;
+		if difference > 0 && self.resents_higher_status {-difference}
+		else {difference}
+	}//end status_reaction()
+
+	/// Sums up the base attitude and reaction modifiers of the NPC.  
+	/// If count_disabled is false, then only reaction modifiers
+	/// with enabled == true will be considered.
+	pub fn reaction_sum(&self, count_disabled: bool) -> i32 {
+		self.reaction_modifiers.iter()
+			.fold(self.base_attitude, |acum, modi| {
+				if count_disabled || modi.enabled {acum + modi.modi}
+				else {acum}
+			})
+	}//end reaction_sum()
+
+	/// Lists the base attitude, if any, and every reaction modifier
+	/// on the NPC, along with whether it counted towards the total.
+	pub fn reaction_breakdown(&self, count_disabled: bool) -> ReactionBreakdown {
+		let mut entries = Vec::new();
+		if self.base_attitude != 0 {
+			entries.push(BreakdownEntry {
+				source: self.name.clone(),
+				modifier: ReactionMod::new("Base Attitude", self.base_attitude),
+				status: EntryStatus::Counted,
+				recognition: None,
+			});
+		}//end if the NPC has a base attitude
+		for modi in self.reaction_modifiers.iter() {
+			entries.push(BreakdownEntry {
+				source: self.name.clone(),
+				modifier: modi.clone(),
+				status: if count_disabled || modi.enabled {EntryStatus::Counted}
+					else {EntryStatus::Skipped(SkipReason::Disabled)},
+				recognition: None,
+			});
+		}//end adding each modifier
+		ReactionBreakdown {
+			entries,
+			total: self.reaction_sum(count_disabled),
+		}//end struct construction
+	}//end reaction_breakdown()
+}//end impl for Npc
+
+/// The ways that the reaction modifiers of several characters
+/// can be combined into one modifier for the whole group.
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub enum PartyRule {
+	/// Only the first character, who does the talking, counts.
+	#[default]
+	Spokesperson,
+	/// The worst modifier in the group counts.
+	Worst,
+	/// The best modifier in the group counts.
+	Best,
+	/// The average of everyone's modifiers, rounded to the nearest whole number.
+	Average,
+	/// Everyone's modifiers are added together.
+	Sum,
+}//end enum PartyRule
+
+impl PartyRule {
+	/// All the party rules, in the order they should be listed.
+	pub const ALL: [PartyRule; 5] = [
+		PartyRule::Spokesperson,
+		PartyRule::Worst,
+		PartyRule::Best,
+		PartyRule::Average,
+		PartyRule::Sum,
+	];
+
+	/// The name of this rule, for display.
+	pub fn name(&self) -> &'static str {
+		match self {
+			PartyRule::Spokesperson => "Spokesperson",
+			PartyRule::Worst => "Worst Of",
+			PartyRule::Best => "Best Of",
+			PartyRule::Average => "Average",
+			PartyRule::Sum => "Sum",
+		}//end matching self to name
+	}//end name()
+
+	/// Combines a list of reaction sums, in party order, into a single modifier.  
+	/// An empty list gives 0.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::PartyRule;
+	/// let sums = [2, -3, 0];
+	/// assert_eq!(2, PartyRule::Spokesperson.combine(&sums));
+	/// assert_eq!(-3, PartyRule::Worst.combine(&sums));
+	/// assert_eq!(2, PartyRule::Best.combine(&sums));
+	/// assert_eq!(0, PartyRule::Average.combine(&sums));
+	/// assert_eq!(-1, PartyRule::Sum.combine(&sums));
+	/// assert_eq!(0, PartyRule::Worst.combine(&[]));
+	/// ```
+	pub fn combine(&self, sums: &[i32]) -> i32 {
+		if sums.is_empty() { return 0; }
+		match self {
+			PartyRule::Spokesperson => sums[0],
+			PartyRule::Worst => *sums.iter().min().unwrap_or(&0),
+			PartyRule::Best => *sums.iter().max().unwrap_or(&0),
+			PartyRule::Average => (sums.iter().sum::<i32>() as f64 / sums.len() as f64).round() as i32,
+			PartyRule::Sum => sums.iter().sum(),
+		}//end matching rule to way of combining
+	}//end combine()
+}//end impl for PartyRule
+
+impl PartyStrategy for PartyRule {
+	fn aggregate(&self, party: &Party, count_disabled: bool) -> PartyReaction {
+		let involved: Vec<(usize, &Character)> = party.involved_members().collect();
+		let sums: Vec<i32> = involved.iter().map(|(_, character)| character.reaction_sum(count_disabled)).collect();
+		let total = match self {
+			// the spokesperson isn't always the first involved character
+			PartyRule::Spokesperson => party.spokesperson().map_or(0, |character| character.reaction_sum(count_disabled)),
+			_ => self.combine(&sums),
+		};
+		let counted: Vec<(usize, &Character)> = match self {
+			PartyRule::Spokesperson => party.spokesperson_index()
+				.map(|index| (index, &party.members[index].character)).into_iter().collect(),
+			PartyRule::Worst => sums.iter().enumerate()
+				.min_by_key(|(_, sum)| **sum)
+				.map(|(index, _)| involved[index]).into_iter().collect(),
+			PartyRule::Best => sums.iter().enumerate()
+				.max_by_key(|(index, sum)| (**sum, std::cmp::Reverse(*index)))
+				.map(|(index, _)| involved[index]).into_iter().collect(),
+			PartyRule::Average | PartyRule::Sum => involved,
+		};
+		PartyReaction {
+			total,
+			contributors: counted.into_iter()
+				.flat_map(|(member, character)| Contribution::from_character(member, character, count_disabled))
+				.collect(),
+		}//end struct construction
+	}//end aggregate()
+}//end impl PartyStrategy for PartyRule
+
+/// A way of combining the reaction modifiers of a party into a single
+/// modifier, keeping track of which modifiers went into it.  
+/// PartyRule covers the usual ways of doing this, and any closure taking
+/// a &Party and count_disabled can be used as a custom rule.
+pub trait PartyStrategy {
+	/// Combines the modifiers of the involved members of the party.
+	/// If count_disabled is false, then only enabled modifiers count.
+	fn aggregate(&self, party: &Party, count_disabled: bool) -> PartyReaction;
+}//end trait PartyStrategy
+
+impl<F> PartyStrategy for F where F: Fn(&Party, bool) -> PartyReaction {
+	fn aggregate(&self, party: &Party, count_disabled: bool) -> PartyReaction {
+		self(party, count_disabled)
+	}//end aggregate()
+}//end impl PartyStrategy for closures
+
+/// One reaction modifier that went into a party's reaction,
+/// along with the character it came from.
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub struct Contribution {
+	/// The index in the party's members of the character it came from.
+	#[serde(default)]
+	pub member: usize,
+	pub character: String,
+	pub modifier: ReactionMod,
+}//end struct Contribution
+
+impl Contribution {
+	/// Gets a contribution for each modifier that counts on the character,
+	/// who is at the index member in the party.
+	pub fn from_character(member: usize, character: &Character, count_disabled: bool) -> Vec<Contribution> {
+		character.reaction_modifiers.iter()
+			.filter(|modi| count_disabled || modi.enabled)
+			.map(|modi| Contribution {
+				member,
+				character: character.name.clone(),
+				modifier: modi.clone(),
+			})
+			.collect()
+	}//end from_character()
+}//end impl for Contribution
+
+/// The combined reaction modifier for a party, along with the
+/// modifiers that contributed to it.
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub struct PartyReaction {
+	pub total: i32,
+	pub contributors: Vec<Contribution>,
+}//end struct PartyReaction
+
+/// A character in a party, and whether they're involved
+/// in the current interaction.
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub struct PartyMember {
+	pub character: Character,
+	pub involved: bool,
+}//end struct PartyMember
+
+/// A group of characters reacted to together.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Character, Party, PartyRule, PartyStrategy, ReactionMod};
+/// let mut bob = Character::new("Bob");
+/// bob.reaction_modifiers.push(ReactionMod::new("Ugly", -6));
+/// bob.reaction_modifiers.push(ReactionMod::new("Kind", 1));
+/// let mut alice = Character::new("Alice");
+/// alice.reaction_modifiers.push(ReactionMod::new("Charisma", 2));
+/// let mut party = Party::from_characters(vec![bob, alice]);
+///
+/// let worst = party.reaction(&PartyRule::Worst, false);
+/// assert_eq!(-5, worst.total);
+/// assert_eq!(2, worst.contributors.len());
+/// assert!(worst.contributors.iter().all(|contribution| contribution.character == "Bob"));
+///
+/// party.spokesperson = 1;
+/// let spokesperson = party.reaction(&PartyRule::Spokesperson, false);
+/// assert_eq!(2, spokesperson.total);
+/// assert_eq!("Charisma", spokesperson.contributors[0].modifier.name);
+///
+/// // custom rules are just closures
+/// let halved = |party: &Party, count_disabled: bool| {
+///     let mut reaction = PartyRule::Sum.aggregate(party, count_disabled);
+///     reaction.total /= 2;
+///     reaction
+/// };
+/// assert_eq!(-1, party.reaction(&halved, false).total);
+/// ```
+#[derive(Clone,PartialEq,Eq,PartialOrd,Ord,Debug,Hash,Default,Serialize,Deserialize)]
+pub struct Party {
+	pub members: Vec<PartyMember>,
+	/// The index in members of the character doing the talking.
+	pub spokesperson: usize,
+}//end struct Party
+
+impl Party {
+	/// Creates a new party with no members.
+	pub fn new() -> Party {
+		Party::default()
+	}//end new()
+
+	/// Creates a new party out of the characters, all of whom are involved.  
+	/// The first character is the spokesperson.
+	pub fn from_characters(characters: Vec<Character>) -> Party {
+		Party {
+			members: characters.into_iter()
+				.map(|character| PartyMember { character, involved: true })
+				.collect(),
+			spokesperson: 0,
+		}//end struct construction
+	}//end from_characters()
+
+	/// Adds a new involved character to the party.
+	pub fn push(&mut self, character: Character) {
+		self.members.push(PartyMember { character, involved: true });
+	}//end push()
+
+	/// Iterates over the characters involved in the current interaction.
+	pub fn involved(&self) -> impl Iterator<Item = &Character> {
+		self.members.iter()
+			.filter(|member| member.involved)
+			.map(|member| &member.character)
+	}//end involved()
+
+	/// Iterates over the characters involved in the current interaction,
+	/// along with their index in members.
+	pub fn involved_members(&self) -> impl Iterator<Item = (usize, &Character)> {
+		self.members.iter().enumerate()
+			.filter(|(_, member)| member.involved)
+			.map(|(index, member)| (index, &member.character))
+	}//end involved_members()
+
+	/// Gets the index in members of the character doing the talking. If the chosen
+	/// spokesperson isn't involved, then the first involved character speaks instead.
+	pub fn spokesperson_index(&self) -> Option<usize> {
+		match self.members.get(self.spokesperson) {
+			Some(member) if member.involved => Some(self.spokesperson),
+			_ => self.involved_members().next().map(|(index, _)| index),
+		}//end matching whether the spokesperson is involved
+	}//end spokesperson_index()
+
+	/// Gets the character doing the talking. If the chosen spokesperson
+	/// isn't involved, then the first involved character speaks instead.
+	pub fn spokesperson(&self) -> Option<&Character> {
+		self.spokesperson_index().map(|index| &self.members[index].character)
+	}//end spokesperson()
+
+	/// Works out the party's reaction modifier using the strategy.
+	/// If count_disabled is false, then only enabled modifiers count.
+	pub fn reaction<S: PartyStrategy + ?Sized>(&self, strategy: &S, count_disabled: bool) -> PartyReaction {
+		strategy.aggregate(self, count_disabled)
+	}//end reaction()
+
+	/// Lists every reaction modifier in the party, along with whether
+	/// the strategy counted it, and if not, why not.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::{Character, Party, PartyRule, ReactionMod};
+	/// # use gurps_reactions::reaction::{EntryStatus, SkipReason};
+	/// let mut bob = Character::new("Bob");
+	/// bob.reaction_modifiers.push(ReactionMod::new("Ugly", -6));
+	/// let mut alice = Character::new("Alice");
+	/// alice.reaction_modifiers.push(ReactionMod::new("Charisma", 2));
+	/// let mut carol = Character::new("Carol");
+	/// carol.reaction_modifiers.push(ReactionMod::new("Kind", 1));
+	/// let mut party = Party::from_characters(vec![bob, alice, carol]);
+	/// party.members[2].involved = false;
+	///
+	/// let breakdown = party.reaction_breakdown(&PartyRule::Best, false);
+	/// assert_eq!(2, breakdown.total);
+	/// assert_eq!(EntryStatus::Skipped(SkipReason::NotChosenByRule), breakdown.entries[0].status);
+	/// assert_eq!(EntryStatus::Counted, breakdown.entries[1].status);
+	/// assert_eq!(EntryStatus::Skipped(SkipReason::NotInvolved), breakdown.entries[2].status);
+	///
+	/// // members with the same name and modifiers are still told apart
+	/// let mut unnamed = Character::new("");
+	/// unnamed.reaction_modifiers.push(ReactionMod::new("New Modifier", 2));
+	/// let party = Party::from_characters(vec![unnamed.clone(), unnamed]);
+	/// let breakdown = party.reaction_breakdown(&PartyRule::Best, false);
+	/// assert_eq!(2, breakdown.total);
+	/// assert_eq!(EntryStatus::Counted, breakdown.entries[0].status);
+	/// assert_eq!(EntryStatus::Skipped(SkipReason::NotChosenByRule), breakdown.entries[1].status);
+	/// ```
+	pub fn reaction_breakdown<S: PartyStrategy + ?Sized>(&self, strategy: &S, count_disabled: bool) -> ReactionBreakdown {
+		let reaction = self.reaction(strategy, count_disabled);
+		let mut entries = Vec::new();
+		for (index, member) in self.members.iter().enumerate() {
+			for modi in member.character.reaction_modifiers.iter() {
+				let contributed = reaction.contributors.iter()
+					.any(|contribution| contribution.member == index && contribution.modifier == *modi);
+				let status = if !member.involved {EntryStatus::Skipped(SkipReason::NotInvolved)}
+					else if !count_disabled && !modi.enabled {EntryStatus::Skipped(SkipReason::Disabled)}
+					else if contributed {EntryStatus::Counted}
+					else {EntryStatus::Skipped(SkipReason::NotChosenByRule)};
+				entries.push(BreakdownEntry {
+					source: member.character.name.clone(),
+					modifier: modi.clone(),
+					status,
+					recognition: None,
+				});
+			}//end looking at each modifier of the member
+		}//end looking at each member
+		ReactionBreakdown {
+			entries,
+			total: reaction.total,
+		}//end struct construction
+	}//end reaction_breakdown()
+}//end impl for Party
+
+/// The contents of a character file, which may hold either
+/// a single character or a list of them.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum CharacterFile {
+	Single(Character),
+	Multiple(Vec<Character>),
+}//end enum CharacterFile
+
+/// Converts characters into the JSON used for character files.  
+/// A single character is written by itself, and anything else
+/// is written as a list.
+pub fn characters_to_json(characters: &[Character]) -> Result<String, CharacterFileError> {
+	let json = match characters {
+		[character] => serde_json::to_string_pretty(character)?,
+		_ => serde_json::to_string_pretty(characters)?,
+	};
+	Ok(json)
+}//end characters_to_json()
+
+/// Reads characters from the JSON used for character files.  
+/// Accepts either a single character or a list of characters.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Character, ReactionMod, characters_from_json, characters_to_json};
+/// let mut bob = Character::new("Bob");
+/// bob.reaction_modifiers.push(ReactionMod::new("Ugly", -6));
+/// let alice = Character::new("Alice");
+///
+/// let single = characters_to_json(&[bob.clone()]).unwrap();
+/// assert_eq!(vec![bob.clone()], characters_from_json(&single).unwrap());
+///
+/// let party = characters_to_json(&[bob.clone(), alice.clone()]).unwrap();
+/// assert_eq!(vec![bob, alice], characters_from_json(&party).unwrap());
+///
+/// assert!(characters_from_json("not a character").is_err());
+/// ```
+pub fn characters_from_json(json: &str) -> Result<Vec<Character>, CharacterFileError> {
+	match serde_json::from_str(json)? {
+		CharacterFile::Single(character) => Ok(vec![character]),
+		CharacterFile::Multiple(characters) => Ok(characters),
+	}//end matching what was in the file
+}//end characters_from_json()
+
+/// Writes characters to a character file at the given path.  
+/// A single character is written by itself so that the file
+/// can be shared as just that character.
+pub fn write_characters<P: AsRef<Path>>(path: P, characters: &[Character]) -> Result<(), CharacterFileError> {
+	let json = characters_to_json(characters)?;
+	fs::write(path, json)?;
+	Ok(())
+}//end write_characters()
+
+/// Reads the characters from a character file at the given path.  
+/// The file can hold either a single character or a list of them.
+pub fn read_characters<P: AsRef<Path>>(path: P) -> Result<Vec<Character>, CharacterFileError> {
+	let json = fs::read_to_string(path)?;
+	characters_from_json(&json)
+}//end read_characters()
+
+/// The ways that reading or writing a character file can fail.
+#[derive(Debug)]
+pub enum CharacterFileError {
+	/// The file couldn't be read or written.
+	Io(io::Error),
+	/// The file didn't hold valid character data.
+	Json(serde_json::Error),
+}//end enum CharacterFileError
+
+impl fmt::Display for CharacterFileError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			CharacterFileError::Io(err) => write!(f, "couldn't access the character file: {}", err),
+			CharacterFileError::Json(err) => write!(f, "the character file isn't valid: {}", err),
+		}//end matching the kind of error
+	}//end fmt()
+}//end impl Display for CharacterFileError
+
+impl std::error::Error for CharacterFileError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			CharacterFileError::Io(err) => Some(err),
+			CharacterFileError::Json(err) => Some(err),
+		}//end matching the kind of error
+	}//end source()
+}//end impl Error for CharacterFileError
+
+impl From<io::Error> for CharacterFileError {
+	fn from(err: io::Error) -> Self { CharacterFileError::Io(err) }
+}//end impl From<io::Error> for CharacterFileError
+
+impl From<serde_json::Error> for CharacterFileError {
+	fn from(err: serde_json::Error) -> Self { CharacterFileError::Json(err) }
+}//end impl From<serde_json::Error> for CharacterFileError
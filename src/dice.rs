@@ -1,9 +1,9 @@
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 /// This struct represents a single die.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
-struct Die {
+pub struct Die {
 	/// The result from initialization.
 	pub result: i32,
 }//end struct Die
@@ -11,14 +11,939 @@ struct Die {
 impl Die {
 	/// Creates a new die with a random result between 1 and 6.
 	pub fn new() -> Die {
+		Die::new_with(&mut rand::thread_rng())
+	}//end new()
+
+	/// Creates a new die with a result between 1 and 6,
+	/// using the provided source of randomness.
+	pub fn new_with<R: Rng + ?Sized>(rng: &mut R) -> Die {
 		Die {
-			result: rand::thread_rng().gen_range(1..=6),
+			result: rng.gen_range(1..=6),
 		}//end struct construction
-	}//end new()
+	}//end new_with()
+
+	/// Creates a die showing a face that was rolled by hand.  
+	/// Fails if the face isn't between 1 and 6.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::dice::Die;
+	/// assert_eq!(4, Die::from_face(4).unwrap().result);
+	/// assert!(Die::from_face(7).is_err());
+	/// assert!(Die::from_face(0).is_err());
+	/// ```
+	pub fn from_face(face: i32) -> Result<Die, DieFaceError> {
+		match face {
+			1..=6 => Ok(Die { result: face }),
+			_ => Err(DieFaceError::new(face, 0)),
+		}//end matching whether the face is on a six-sided die
+	}//end from_face()
 
 	/// Re-randomizes the result to a new value between 1 and 6.  
 	/// There is not check to make sure the new value is different; it's random.
 	pub fn reroll(&mut self) {
-		self.result = rand::thread_rng().gen_range(1..=6);
+		self.reroll_with(&mut rand::thread_rng());
 	}//end reroll()
+
+	/// Re-randomizes the result using the provided source of randomness.
+	pub fn reroll_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+		self.result = rng.gen_range(1..=6);
+	}//end reroll_with()
 }//end impl for Die
+
+impl Default for Die {
+	/// A die showing a 1. Use Die::new() for a random result.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::dice::Die;
+	/// assert_eq!(Die {result: 1}, Die::default());
+	/// ```
+	fn default() -> Self { Die {result: 1} }
+}//end impl Default for Die
+
+/// This struct represents a roll of several dice, along with
+/// a flat modifier applied to the sum of those dice.
+/// The individual dice are kept around so they can be shown.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct Roll {
+	/// The individual dice that were rolled.
+	pub dice: Vec<Die>,
+	/// The modifier that was applied to the sum of the dice.
+	pub modifier: i32,
+	/// The sum of the dice plus the modifier.
+	pub total: i32,
+	/// The seed the dice were rolled from, if they came from a RollSession.
+	/// Can be used to replay the roll.
+	#[serde(default)]
+	pub seed: Option<u64>,
+}//end struct Roll
+
+impl Roll {
+	/// Rolls the given number of dice and applies the modifier.
+	pub fn new(count: usize, modifier: i32) -> Roll {
+		Roll::new_with(count, modifier, &mut rand::thread_rng())
+	}//end new()
+
+	/// Rolls the given number of dice and applies the modifier,
+	/// using the provided source of randomness.
+	pub fn new_with<R: Rng + ?Sized>(count: usize, modifier: i32, rng: &mut R) -> Roll {
+		let dice = (0..count).map(|_| Die::new_with(rng)).collect();
+		Roll::from_dice(dice, modifier)
+	}//end new_with()
+
+	/// Rolls the given number of dice from a specific seed.
+	/// The seed is recorded on the returned roll.
+	pub fn from_seed(count: usize, modifier: i32, seed: u64) -> Roll {
+		let mut roll = Roll::new_with(count, modifier, &mut StdRng::seed_from_u64(seed));
+		roll.seed = Some(seed);
+		roll
+	}//end from_seed()
+
+	/// Rolls 3d6 and applies the modifier, as for a reaction roll.
+	pub fn roll_3d6(modifier: i32) -> Roll {
+		Roll::new(3, modifier)
+	}//end roll_3d6()
+
+	/// Creates a roll out of dice that have already been rolled,
+	/// calculating the total from them and the modifier.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::dice::{Die, Roll};
+	/// let dice = vec![Die {result: 2}, Die {result: 5}, Die {result: 6}];
+	/// let roll = Roll::from_dice(dice, -3);
+	/// assert_eq!(13, roll.dice_sum());
+	/// assert_eq!(-3, roll.modifier);
+	/// assert_eq!(10, roll.total);
+	///
+	/// // totals stop at the limits instead of overflowing
+	/// let roll = Roll::from_dice(vec![Die {result: i32::MAX}], 1);
+	/// assert_eq!(i32::MAX, roll.total);
+	/// ```
+	pub fn from_dice(dice: Vec<Die>, modifier: i32) -> Roll {
+		let dice_sum = saturating_sum(&dice);
+		Roll {
+			dice,
+			modifier,
+			total: dice_sum.saturating_add(modifier),
+			seed: None,
+		}//end struct construction
+	}//end from_dice()
+
+	/// Creates a roll out of faces that were rolled by hand, applying the modifier.  
+	/// Fails on the first face that isn't between 1 and 6.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::dice::Roll;
+	/// let roll = Roll::from_faces(&[6, 2, 3], 1).unwrap();
+	/// assert_eq!(12, roll.total);
+	/// assert_eq!(None, roll.seed);
+	/// let err = Roll::from_faces(&[6, 9, 3], 1).unwrap_err();
+	/// assert_eq!(9, err.face);
+	/// assert_eq!(1, err.position);
+	/// ```
+	pub fn from_faces(faces: &[i32], modifier: i32) -> Result<Roll, DieFaceError> {
+		let dice = faces.iter().enumerate()
+			.map(|(position, face)| Die::from_face(*face).map_err(|err| DieFaceError::new(err.face, position)))
+			.collect::<Result<Vec<Die>, DieFaceError>>()?;
+		Ok(Roll::from_dice(dice, modifier))
+	}//end from_faces()
+
+	/// Sums up the dice in this roll, without the modifier.
+	pub fn dice_sum(&self) -> i32 {
+		saturating_sum(&self.dice)
+	}//end dice_sum()
+
+	/// Rolls the same dice again from the recorded seed, which should
+	/// give the exact same result. Returns None if there is no seed.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::dice::RollSession;
+	/// let mut session = RollSession::seeded(42);
+	/// let roll = session.roll_3d6(2);
+	/// assert_eq!(Some(roll.clone()), roll.replay());
+	/// ```
+	pub fn replay(&self) -> Option<Roll> {
+		self.seed.map(|seed| Roll::from_seed(self.dice.len(), self.modifier, seed))
+	}//end replay()
+}//end impl for Roll
+
+/// Shows each die, then the modifier if there is one, then the total.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::{Die, Roll};
+/// let roll = Roll::from_dice(vec![Die {result: 5}, Die {result: 4}, Die {result: 4}], -3);
+/// assert_eq!("5+4+4-3 = 10", roll.to_string());
+/// ```
+impl std::fmt::Display for Roll {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let dice: Vec<String> = self.dice.iter().map(|die| die.result.to_string()).collect();
+		write!(f, "{}", dice.join("+"))?;
+		if self.modifier != 0 { write!(f, "{:+}", self.modifier)?; }
+		write!(f, " = {}", self.total)
+	}//end fmt()
+}//end impl Display for Roll
+
+/// A source of rolls that can be reproduced from a single seed.  
+/// Each roll made from the session gets its own seed drawn from the
+/// session, and that seed is recorded on the roll, so a single roll
+/// can be replayed without replaying the whole session.
+///
+/// Seeds are only reproducible with the same version of the rand crate.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::RollSession;
+/// let mut first = RollSession::seeded(1234);
+/// let mut second = RollSession::seeded(1234);
+/// for _ in 0..10 {
+///     assert_eq!(first.roll_3d6(0), second.roll_3d6(0));
+/// }
+/// assert_eq!(1234, first.seed());
+/// ```
+#[derive(Clone, Debug)]
+pub struct RollSession {
+	seed: u64,
+	rng: StdRng,
+}//end struct RollSession
+
+impl RollSession {
+	/// Creates a new session with a random seed.
+	pub fn new() -> RollSession {
+		RollSession::seeded(rand::thread_rng().gen())
+	}//end new()
+
+	/// Creates a new session from a specific seed.
+	pub fn seeded(seed: u64) -> RollSession {
+		RollSession {
+			seed,
+			rng: StdRng::seed_from_u64(seed),
+		}//end struct construction
+	}//end seeded()
+
+	/// The seed this session was created with.
+	pub fn seed(&self) -> u64 { self.seed }
+
+	/// Rolls the given number of dice and applies the modifier.
+	pub fn roll(&mut self, count: usize, modifier: i32) -> Roll {
+		let roll_seed = self.rng.gen();
+		Roll::from_seed(count, modifier, roll_seed)
+	}//end roll()
+
+	/// Rolls 3d6 and applies the modifier.
+	pub fn roll_3d6(&mut self, modifier: i32) -> Roll {
+		self.roll(3, modifier)
+	}//end roll_3d6()
+
+	/// Rolls the dice described by the expression.
+	pub fn roll_expr(&mut self, expr: &DiceExpr) -> Roll {
+		self.roll(expr.count, expr.modifier)
+	}//end roll_expr()
+
+	/// Rolls the given number of dice several times, keeping one of the rolls.
+	pub fn roll_keeping(&mut self, times: usize, count: usize, modifier: i32, keep: KeepRule) -> KeptRoll {
+		let rolls = (0..times.max(1)).map(|_| self.roll(count, modifier)).collect();
+		KeptRoll::new(rolls, keep)
+	}//end roll_keeping()
+
+	/// Rolls 3d6 against the target number.
+	pub fn success_roll(&mut self, target: i32) -> SuccessRoll {
+		SuccessRoll::new(target, self.roll_3d6(0))
+	}//end success_roll()
+}//end impl for RollSession
+
+impl Default for RollSession {
+	fn default() -> Self { RollSession::new() }
+}//end impl Default for RollSession
+
+/// The most dice a Distribution can be worked out for, since
+/// the number of ways to roll any more wouldn't fit in a u64.
+pub const MAX_DISTRIBUTION_DICE: usize = 24;
+
+/// The exact distribution of totals for a number of six-sided dice
+/// plus a modifier, stored as the number of ways to roll each total.  
+/// This is calculated by counting, so there's no sampling noise.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::{Distribution, MAX_DISTRIBUTION_DICE};
+/// let three_d = Distribution::new(3, 0).unwrap();
+/// assert_eq!(216, three_d.outcomes());
+/// assert_eq!(27, three_d.ways(10));
+/// assert_eq!(1, three_d.ways(18));
+/// assert_eq!(0, three_d.ways(19));
+/// assert!((three_d.probability_at_least(10) - 0.625).abs() < 1e-12);
+/// assert!((three_d.probability_at_most(4) - 4.0 / 216.0).abs() < 1e-12);
+///
+/// // the counts stop fitting past MAX_DISTRIBUTION_DICE
+/// let most = Distribution::new(MAX_DISTRIBUTION_DICE, 0).unwrap();
+/// assert_eq!(6_u64.pow(24), most.outcomes());
+/// assert!(Distribution::new(MAX_DISTRIBUTION_DICE + 1, 0).is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub struct Distribution {
+	/// The lowest possible total.
+	pub min: i32,
+	/// The number of ways to get each total, starting from min.
+	pub counts: Vec<u64>,
+}//end struct Distribution
+
+impl Distribution {
+	/// Calculates the distribution of totals for count six-sided
+	/// dice with the modifier added on.  
+	/// Fails if count is more than MAX_DISTRIBUTION_DICE.
+	pub fn new(count: usize, modifier: i32) -> Result<Distribution, TooManyDiceError> {
+		if count > MAX_DISTRIBUTION_DICE { return Err(TooManyDiceError::new(count)); }
+		Ok(Distribution::counted(count, modifier))
+	}//end new()
+
+	/// Calculates the distribution by counting, without checking
+	/// whether the counts will fit.
+	fn counted(count: usize, modifier: i32) -> Distribution {
+		// counts[i] is the number of ways for the dice so far to sum to i
+		let mut counts = vec![1_u64];
+		for _ in 0..count {
+			let mut next = vec![0_u64; counts.len() + 6];
+			for (sum, ways) in counts.iter().enumerate() {
+				for face in 1..=6 { next[sum + face] += ways; }
+			}//end adding each face to each previous sum
+			counts = next;
+		}//end adding one die at a time
+		// the lowest sum is count, so trim off the impossible ones
+		let counts = counts.split_off(count);
+		Distribution {
+			min: i32::try_from(count).unwrap_or(i32::MAX).saturating_add(modifier),
+			counts,
+		}//end struct construction
+	}//end counted()
+
+	/// The distribution of an unmodified 3d6 roll.
+	pub fn three_d6() -> Distribution { Distribution::counted(3, 0) }
+
+	/// The highest possible total.
+	pub fn max(&self) -> i32 { self.min.saturating_add(self.counts.len() as i32 - 1) }
+
+	/// The total number of equally likely outcomes.
+	pub fn outcomes(&self) -> u64 { self.counts.iter().sum() }
+
+	/// The number of outcomes which give this exact total.
+	pub fn ways(&self, total: i32) -> u64 {
+		if total < self.min || total > self.max() { 0 }
+		else { self.counts[(total - self.min) as usize] }
+	}//end ways()
+
+	/// The number of outcomes which give a total within the range.
+	pub fn ways_in(&self, range: std::ops::RangeInclusive<i32>) -> u64 {
+		let low = (*range.start()).max(self.min);
+		let high = (*range.end()).min(self.max());
+		(low..=high).map(|total| self.ways(total)).sum()
+	}//end ways_in()
+
+	/// The probability of rolling this exact total.
+	pub fn probability(&self, total: i32) -> f64 {
+		self.ways(total) as f64 / self.outcomes() as f64
+	}//end probability()
+
+	/// The probability of rolling a total within the range.
+	pub fn probability_in(&self, range: std::ops::RangeInclusive<i32>) -> f64 {
+		self.ways_in(range) as f64 / self.outcomes() as f64
+	}//end probability_in()
+
+	/// The probability of rolling the total or higher.
+	pub fn probability_at_least(&self, total: i32) -> f64 {
+		self.probability_in(total..=i32::MAX)
+	}//end probability_at_least()
+
+	/// The probability of rolling the total or lower.
+	pub fn probability_at_most(&self, total: i32) -> f64 {
+		self.probability_in(i32::MIN..=total)
+	}//end probability_at_most()
+
+	/// Iterates over each possible total along with the number of ways to get it.
+	pub fn iter(&self) -> impl Iterator<Item = (i32, u64)> + '_ {
+		self.counts.iter().enumerate().map(|(offset, ways)| (self.min.saturating_add(offset as i32), *ways))
+	}//end iter()
+}//end impl for Distribution
+
+/// Which roll to keep when rolling several times.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub enum KeepRule {
+	/// Keep the roll with the highest total, which is best for a reaction roll.
+	#[default]
+	Highest,
+	/// Keep the roll with the lowest total, which is best for a success roll.
+	Lowest,
+}//end enum KeepRule
+
+/// The result of rolling several times and keeping one of the rolls,
+/// as with Luck. The discarded rolls are kept around too.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::{KeepRule, KeptRoll, RollSession};
+/// let mut session = RollSession::seeded(99);
+/// let lucky = session.roll_keeping(3, 3, 0, KeepRule::Highest);
+/// assert_eq!(3, lucky.rolls.len());
+/// assert_eq!(2, lucky.discarded().count());
+/// assert!(lucky.rolls.iter().all(|roll| roll.total <= lucky.kept().total));
+///
+/// let cursed = KeptRoll::new(lucky.rolls.clone(), KeepRule::Lowest);
+/// assert!(cursed.rolls.iter().all(|roll| roll.total >= cursed.kept().total));
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct KeptRoll {
+	/// Every roll that was made, in the order they were made.
+	pub rolls: Vec<Roll>,
+	/// Which roll was kept.
+	pub keep: KeepRule,
+	/// The index in rolls of the one that was kept.
+	pub kept_index: usize,
+}//end struct KeptRoll
+
+impl KeptRoll {
+	/// Picks which of the rolls to keep. On a tie, the earlier roll is kept.  
+	/// If there are no rolls, a roll of no dice is kept.
+	pub fn new(mut rolls: Vec<Roll>, keep: KeepRule) -> KeptRoll {
+		if rolls.is_empty() { rolls.push(Roll::from_dice(Vec::new(), 0)); }
+		let mut kept_index = 0;
+		for (index, roll) in rolls.iter().enumerate() {
+			let better = match keep {
+				KeepRule::Highest => roll.total > rolls[kept_index].total,
+				KeepRule::Lowest => roll.total < rolls[kept_index].total,
+			};
+			if better { kept_index = index; }
+		}//end looking for the roll to keep
+		KeptRoll {
+			rolls,
+			keep,
+			kept_index,
+		}//end struct construction
+	}//end new()
+
+	/// The roll that was kept.
+	pub fn kept(&self) -> &Roll { &self.rolls[self.kept_index] }
+
+	/// The rolls that were thrown away.
+	pub fn discarded(&self) -> impl Iterator<Item = &Roll> {
+		self.rolls.iter().enumerate()
+			.filter(move |(index, _)| *index != self.kept_index)
+			.map(|(_, roll)| roll)
+	}//end discarded()
+}//end impl for KeptRoll
+
+/// How a roll against a target number turned out.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum SuccessOutcome {
+	CriticalFailure,
+	Failure,
+	Success,
+	CriticalSuccess,
+}//end enum SuccessOutcome
+
+impl SuccessOutcome {
+	/// Whether this is a success of either kind.
+	pub fn is_success(&self) -> bool {
+		matches!(self, SuccessOutcome::Success | SuccessOutcome::CriticalSuccess)
+	}//end is_success()
+
+	/// Whether this is a critical success or critical failure.
+	pub fn is_critical(&self) -> bool {
+		matches!(self, SuccessOutcome::CriticalSuccess | SuccessOutcome::CriticalFailure)
+	}//end is_critical()
+
+	/// The name of this outcome, for display.
+	pub fn name(&self) -> &'static str {
+		match self {
+			SuccessOutcome::CriticalFailure => "Critical Failure",
+			SuccessOutcome::Failure => "Failure",
+			SuccessOutcome::Success => "Success",
+			SuccessOutcome::CriticalSuccess => "Critical Success",
+		}//end matching self to name
+	}//end name()
+}//end impl for SuccessOutcome
+
+impl std::fmt::Display for SuccessOutcome {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}//end fmt()
+}//end impl Display for SuccessOutcome
+
+/// A roll of 3d6 against a target number, like a skill or attribute,
+/// using the rules for criticals from B347-348.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::{Die, Roll, SuccessOutcome, SuccessRoll};
+/// let roll = |a, b, c| Roll::from_dice(vec![Die {result: a}, Die {result: b}, Die {result: c}], 0);
+/// let check = SuccessRoll::new(12, roll(3, 4, 2));
+/// assert_eq!(SuccessOutcome::Success, check.outcome());
+/// assert_eq!(3, check.margin());
+/// assert_eq!(3, check.dice().len());
+///
+/// // a 5 is only critical for a target of 15 or more
+/// assert_eq!(SuccessOutcome::Success, SuccessRoll::new(14, roll(1, 1, 3)).outcome());
+/// assert_eq!(SuccessOutcome::CriticalSuccess, SuccessRoll::new(15, roll(1, 1, 3)).outcome());
+/// // a 17 always fails, and is critical for a target of 15 or less
+/// assert_eq!(SuccessOutcome::Failure, SuccessRoll::new(16, roll(6, 6, 5)).outcome());
+/// assert_eq!(SuccessOutcome::CriticalFailure, SuccessRoll::new(15, roll(6, 6, 5)).outcome());
+/// // failing by 10 or more is critical
+/// assert_eq!(SuccessOutcome::CriticalFailure, SuccessRoll::new(3, roll(6, 6, 1)).outcome());
+///
+/// // extreme targets stop at the limits instead of overflowing
+/// assert_eq!(SuccessOutcome::Success, SuccessRoll::new(i32::MAX, roll(3, 4, 2)).outcome());
+/// assert_eq!(i32::MIN, SuccessRoll::new(i32::MIN, roll(3, 4, 2)).margin());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct SuccessRoll {
+	/// The number to roll at or under, such as an effective skill level.
+	pub target: i32,
+	/// The 3d6 roll made against the target.
+	pub roll: Roll,
+}//end struct SuccessRoll
+
+impl SuccessRoll {
+	/// Creates a success roll out of a roll that's already been made.
+	pub fn new(target: i32, roll: Roll) -> SuccessRoll {
+		SuccessRoll {
+			target,
+			roll,
+		}//end struct construction
+	}//end new()
+
+	/// Rolls 3d6 against the target number.
+	pub fn roll(target: i32) -> SuccessRoll {
+		SuccessRoll::new(target, Roll::roll_3d6(0))
+	}//end roll()
+
+	/// Rolls 3d6 against the target number with the provided rng.
+	pub fn roll_with<R: Rng + ?Sized>(target: i32, rng: &mut R) -> SuccessRoll {
+		SuccessRoll::new(target, Roll::new_with(3, 0, rng))
+	}//end roll_with()
+
+	/// The total that was rolled.
+	pub fn total(&self) -> i32 { self.roll.total }
+
+	/// The individual dice that were rolled.
+	pub fn dice(&self) -> &[Die] { &self.roll.dice }
+
+	/// How far under the target the roll was.
+	/// Negative if the roll was over the target.
+	pub fn margin(&self) -> i32 { self.target.saturating_sub(self.total()) }
+
+	/// Works out whether the roll succeeded, and whether it was critical.
+	pub fn outcome(&self) -> SuccessOutcome {
+		let total = self.total();
+		if total <= 4 || (total == 5 && self.target >= 15) || (total == 6 && self.target >= 16) {
+			SuccessOutcome::CriticalSuccess
+		} else if total >= 18 || (total == 17 && self.target <= 15) || total >= self.target.saturating_add(10) {
+			SuccessOutcome::CriticalFailure
+		} else if total >= 17 || total > self.target {
+			SuccessOutcome::Failure
+		} else {
+			SuccessOutcome::Success
+		}//end checking which outcome the total falls into
+	}//end outcome()
+
+	/// Whether the roll succeeded, critically or not.
+	pub fn succeeded(&self) -> bool { self.outcome().is_success() }
+
+	/// Whether the roll was a critical success or failure.
+	pub fn is_critical(&self) -> bool { self.outcome().is_critical() }
+}//end impl for SuccessRoll
+
+impl std::fmt::Display for SuccessRoll {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let margin = self.margin();
+		let by = if self.succeeded() {margin.max(0)} else {(-margin).max(0)};
+		write!(f, "rolled {} vs {}: {} by {}", self.total(), self.target, self.outcome(), by)
+	}//end fmt()
+}//end impl Display for SuccessRoll
+
+/// Which side won a Quick Contest.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum ContestWinner {
+	First,
+	Second,
+	Tie,
+}//end enum ContestWinner
+
+/// A Quick Contest (B348), where each side makes a success roll
+/// and the results are compared, such as an influence skill versus Will.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::{ContestWinner, Die, QuickContest, Roll, SuccessRoll};
+/// let roll = |a, b, c| Roll::from_dice(vec![Die {result: a}, Die {result: b}, Die {result: c}], 0);
+/// // both succeed, but the first succeeds by more
+/// let contest = QuickContest::new(SuccessRoll::new(14, roll(3, 3, 3)), SuccessRoll::new(10, roll(3, 3, 2)));
+/// assert_eq!(ContestWinner::First, contest.winner());
+/// assert_eq!(3, contest.margin_of_victory());
+/// // only the second succeeds
+/// let contest = QuickContest::new(SuccessRoll::new(9, roll(4, 4, 4)), SuccessRoll::new(12, roll(4, 4, 4)));
+/// assert_eq!(ContestWinner::Second, contest.winner());
+/// // extreme targets don't overflow
+/// let contest = QuickContest::new(SuccessRoll::new(i32::MAX, roll(4, 4, 4)), SuccessRoll::new(i32::MIN, roll(4, 4, 4)));
+/// assert_eq!(i32::MAX, contest.margin_of_victory());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct QuickContest {
+	/// The roll of the side acting, such as the influence skill.
+	pub first: SuccessRoll,
+	/// The roll of the side resisting, such as Will.
+	pub second: SuccessRoll,
+}//end struct QuickContest
+
+impl QuickContest {
+	/// Creates a quick contest out of rolls that have already been made.
+	pub fn new(first: SuccessRoll, second: SuccessRoll) -> QuickContest {
+		QuickContest {
+			first,
+			second,
+		}//end struct construction
+	}//end new()
+
+	/// Rolls both sides of a quick contest using the session.
+	pub fn roll(first_target: i32, second_target: i32, session: &mut RollSession) -> QuickContest {
+		QuickContest::new(session.success_roll(first_target), session.success_roll(second_target))
+	}//end roll()
+
+	/// Works out who won. If only one side succeeded, they win.
+	/// Otherwise, whoever succeeded by more, or failed by less, wins.
+	pub fn winner(&self) -> ContestWinner {
+		match (self.first.succeeded(), self.second.succeeded()) {
+			(true, false) => ContestWinner::First,
+			(false, true) => ContestWinner::Second,
+			_ => match self.first.margin().cmp(&self.second.margin()) {
+				std::cmp::Ordering::Greater => ContestWinner::First,
+				std::cmp::Ordering::Less => ContestWinner::Second,
+				std::cmp::Ordering::Equal => ContestWinner::Tie,
+			},
+		}//end matching which sides succeeded
+	}//end winner()
+
+	/// The difference between the margins of the two sides.
+	pub fn margin_of_victory(&self) -> i32 {
+		self.first.margin().saturating_sub(self.second.margin()).saturating_abs()
+	}//end margin_of_victory()
+}//end impl for QuickContest
+
+/// The most dice a parsed dice expression may roll.
+pub const MAX_DICE_COUNT: u32 = 100;
+/// The largest modifier, positive or negative, a parsed dice expression may have.
+pub const MAX_DICE_MODIFIER: u32 = 1000;
+
+/// A specification for a roll, written the way GURPS writes dice.
+/// "3d" or "3d6" is three six-sided dice, and "2d-1" is two dice with
+/// a -1 applied to the total.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::DiceExpr;
+/// let expr: DiceExpr = "2d-1".parse().unwrap();
+/// assert_eq!(2, expr.count);
+/// assert_eq!(-1, expr.modifier);
+/// assert_eq!("2d-1", expr.to_string());
+///
+/// let roll = expr.roll();
+/// assert_eq!(2, roll.dice.len());
+/// assert_eq!(roll.dice_sum() - 1, roll.total);///
+/// assert_eq!(11, expr.distribution().unwrap().max());
+/// assert!("30d".parse::<DiceExpr>().unwrap().distribution().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct DiceExpr {
+	/// The number of six-sided dice to roll.
+	pub count: usize,
+	/// The flat modifier applied to the sum of the dice.
+	pub modifier: i32,
+}//end struct DiceExpr
+
+impl DiceExpr {
+	/// Creates a new dice expression for count dice with the given modifier.
+	pub fn new(count: usize, modifier: i32) -> DiceExpr {
+		DiceExpr { count, modifier }
+	}//end new()
+
+	/// Parses a dice expression in GURPS notation, such as "3d6", "2d-1",
+	/// "1d+2", or "3d6+4". Whitespace around the parts is ignored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::dice::{DiceExpr, DiceParseErrorKind};
+	/// assert_eq!(DiceExpr::new(3, 4), DiceExpr::parse("3d6 + 4").unwrap());
+	/// assert_eq!(DiceExpr::new(1, 2), DiceExpr::parse("1D+2").unwrap());
+	///
+	/// let err = DiceExpr::parse("3d8").unwrap_err();
+	/// assert_eq!(DiceParseErrorKind::UnsupportedSides(8), err.kind);
+	/// assert_eq!(2, err.position);
+	///
+	/// let err = DiceExpr::parse("2d-").unwrap_err();
+	/// assert_eq!(DiceParseErrorKind::ExpectedNumber, err.kind);
+	/// assert_eq!(3, err.position);
+	///
+	/// let err = DiceExpr::parse("4000000000d").unwrap_err();
+	/// assert_eq!(DiceParseErrorKind::NumberTooLarge, err.kind);
+	/// assert_eq!(0, err.position);
+	///
+	/// let err = DiceExpr::parse("1d+2147483647").unwrap_err();
+	/// assert_eq!(DiceParseErrorKind::NumberTooLarge, err.kind);
+	/// assert_eq!(3, err.position);
+	/// ```
+	pub fn parse(text: &str) -> Result<DiceExpr, DiceParseError> {
+		let mut scanner = Scanner::new(text);
+		scanner.skip_whitespace();
+		if scanner.peek().is_none() {
+			return Err(DiceParseError::new(DiceParseErrorKind::Empty, scanner.pos));
+		}//end if there's nothing to parse
+
+		// number of dice
+		let count_pos = scanner.pos;
+		let count = match scanner.number()? {
+			Some(count) => count,
+			None => return Err(DiceParseError::new(DiceParseErrorKind::ExpectedNumber, count_pos)),
+		};
+		if count == 0 {
+			return Err(DiceParseError::new(DiceParseErrorKind::NoDice, count_pos));
+		}//end if no dice are rolled
+		if count > MAX_DICE_COUNT {
+			return Err(DiceParseError::new(DiceParseErrorKind::NumberTooLarge, count_pos));
+		}//end if too many dice are rolled
+
+		// the d, with an optional number of sides
+		scanner.skip_whitespace();
+		match scanner.peek() {
+			Some('d') | Some('D') => scanner.advance(),
+			Some(c) => return Err(DiceParseError::new(DiceParseErrorKind::UnexpectedCharacter(c), scanner.pos)),
+			None => return Err(DiceParseError::new(DiceParseErrorKind::ExpectedD, scanner.pos)),
+		}//end matching the d
+		let sides_pos = scanner.pos;
+		if let Some(sides) = scanner.number()? {
+			if sides != 6 {
+				return Err(DiceParseError::new(DiceParseErrorKind::UnsupportedSides(sides), sides_pos));
+			}//end if the dice aren't d6
+		}//end if sides were specified
+
+		// the optional modifier
+		scanner.skip_whitespace();
+		let modifier = match scanner.peek() {
+			None => 0,
+			Some(sign @ ('+' | '-')) => {
+				scanner.advance();
+				scanner.skip_whitespace();
+				let num_pos = scanner.pos;
+				let magnitude = match scanner.number()? {
+					Some(num) => num,
+					None => return Err(DiceParseError::new(DiceParseErrorKind::ExpectedNumber, num_pos)),
+				};
+				if magnitude > MAX_DICE_MODIFIER {
+					return Err(DiceParseError::new(DiceParseErrorKind::NumberTooLarge, num_pos));
+				}//end if the modifier is too large
+				let magnitude = magnitude as i32;
+				if sign == '-' {-magnitude} else {magnitude}
+			},
+			Some(c) => return Err(DiceParseError::new(DiceParseErrorKind::UnexpectedCharacter(c), scanner.pos)),
+		};
+
+		// make sure nothing is left over
+		scanner.skip_whitespace();
+		if let Some(c) = scanner.peek() {
+			return Err(DiceParseError::new(DiceParseErrorKind::UnexpectedCharacter(c), scanner.pos));
+		}//end if there's trailing junk
+
+		Ok(DiceExpr {
+			count: count as usize,
+			modifier,
+		})//end struct construction
+	}//end parse()
+
+	/// Rolls the dice described by this expression, keeping the
+	/// individual dice in the returned Roll.
+	pub fn roll(&self) -> Roll {
+		Roll::new(self.count, self.modifier)
+	}//end roll()
+
+	/// Rolls the dice described by this expression,
+	/// using the provided source of randomness.
+	pub fn roll_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Roll {
+		Roll::new_with(self.count, self.modifier, rng)
+	}//end roll_with()
+
+	/// Calculates the exact distribution of totals for this expression.  
+	/// Fails if there are more than MAX_DISTRIBUTION_DICE dice.
+	pub fn distribution(&self) -> Result<Distribution, TooManyDiceError> {
+		Distribution::new(self.count, self.modifier)
+	}//end distribution()
+}//end impl for DiceExpr
+
+impl std::str::FromStr for DiceExpr {
+	type Err = DiceParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> { DiceExpr::parse(s) }
+}//end impl FromStr for DiceExpr
+
+impl std::fmt::Display for DiceExpr {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}d", self.count)?;
+		if self.modifier != 0 { write!(f, "{:+}", self.modifier)?; }
+		Ok(())
+	}//end fmt()
+}//end impl Display for DiceExpr
+
+/// The different ways that parsing a dice expression can fail.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum DiceParseErrorKind {
+	/// The expression was empty or only whitespace.
+	Empty,
+	/// A number was expected, such as the count of dice or the modifier.
+	ExpectedNumber,
+	/// The "d" between the number of dice and the modifier was missing.
+	ExpectedD,
+	/// A character showed up that doesn't belong there.
+	UnexpectedCharacter(char),
+	/// Dice with a number of sides other than 6 were requested.
+	UnsupportedSides(u32),
+	/// The expression asked for zero dice.
+	NoDice,
+	/// A number was too large to fit, or more than the
+	/// limits of MAX_DICE_COUNT or MAX_DICE_MODIFIER.
+	NumberTooLarge,
+}//end enum DiceParseErrorKind
+
+/// An error from parsing a dice expression, along with the
+/// byte offset in the input where the problem was found.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct DiceParseError {
+	/// What went wrong.
+	pub kind: DiceParseErrorKind,
+	/// The byte offset into the input where it went wrong.
+	pub position: usize,
+}//end struct DiceParseError
+
+impl DiceParseError {
+	/// Creates a new parse error of the given kind at the given position.
+	pub fn new(kind: DiceParseErrorKind, position: usize) -> DiceParseError {
+		DiceParseError { kind, position }
+	}//end new()
+}//end impl for DiceParseError
+
+impl std::fmt::Display for DiceParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.kind {
+			DiceParseErrorKind::Empty => write!(f, "empty dice expression"),
+			DiceParseErrorKind::ExpectedNumber => write!(f, "expected a number at position {}", self.position),
+			DiceParseErrorKind::ExpectedD => write!(f, "expected 'd' at position {}", self.position),
+			DiceParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}' at position {}", c, self.position),
+			DiceParseErrorKind::UnsupportedSides(sides) => write!(f, "only six-sided dice are supported, found d{} at position {}", sides, self.position),
+			DiceParseErrorKind::NoDice => write!(f, "at least one die must be rolled, see position {}", self.position),
+			DiceParseErrorKind::NumberTooLarge => write!(f, "number too large at position {}", self.position),
+		}//end matching kind of error
+	}//end fmt()
+}//end impl Display for DiceParseError
+
+impl std::error::Error for DiceParseError {}
+
+/// An error from a die face entered by hand that couldn't
+/// have been rolled on a six-sided die.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct DieFaceError {
+	/// The face that was given.
+	pub face: i32,
+	/// Which die in the roll it was, counting from 0.
+	pub position: usize,
+}//end struct DieFaceError
+
+impl DieFaceError {
+	/// Creates a new die face error for the given face and position.
+	pub fn new(face: i32, position: usize) -> DieFaceError {
+		DieFaceError { face, position }
+	}//end new()
+}//end impl for DieFaceError
+
+impl std::fmt::Display for DieFaceError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "die {} must be between 1 and 6, but was {}", self.position + 1, self.face)
+	}//end fmt()
+}//end impl Display for DieFaceError
+
+impl std::error::Error for DieFaceError {}
+
+/// An error from asking for the distribution of more
+/// dice than MAX_DISTRIBUTION_DICE.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct TooManyDiceError {
+	/// The number of dice that were asked for.
+	pub count: usize,
+}//end struct TooManyDiceError
+
+impl TooManyDiceError {
+	/// Creates a new error for the given number of dice.
+	pub fn new(count: usize) -> TooManyDiceError {
+		TooManyDiceError { count }
+	}//end new()
+}//end impl for TooManyDiceError
+
+impl std::fmt::Display for TooManyDiceError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "can't work out the distribution of {} dice, the most is {}", self.count, MAX_DISTRIBUTION_DICE)
+	}//end fmt()
+}//end impl Display for TooManyDiceError
+
+impl std::error::Error for TooManyDiceError {}
+
+/// Sums up the results of the dice, stopping at the limits of i32
+/// rather than overflowing on dice that were built by hand.
+fn saturating_sum(dice: &[Die]) -> i32 {
+	dice.iter().fold(0, |sum, die| sum.saturating_add(die.result))
+}//end saturating_sum()
+
+/// Simple helper for walking through a dice expression one character
+/// at a time while keeping track of the position.
+struct Scanner<'a> {
+	text: &'a str,
+	pos: usize,
+}//end struct Scanner
+
+impl<'a> Scanner<'a> {
+	fn new(text: &'a str) -> Scanner<'a> {
+		Scanner { text, pos: 0 }
+	}//end new()
+
+	/// Gets the next character without moving past it.
+	fn peek(&self) -> Option<char> { self.text[self.pos..].chars().next() }
+
+	/// Moves past the next character.
+	fn advance(&mut self) {
+		if let Some(c) = self.peek() { self.pos += c.len_utf8(); }
+	}//end advance()
+
+	fn skip_whitespace(&mut self) {
+		while self.peek().is_some_and(char::is_whitespace) { self.advance(); }
+	}//end skip_whitespace()
+
+	/// Reads an unsigned number, if there is one at the current position.
+	fn number(&mut self) -> Result<Option<u32>, DiceParseError> {
+		let start = self.pos;
+		while self.peek().is_some_and(|c| c.is_ascii_digit()) { self.advance(); }
+		if start == self.pos { return Ok(None); }
+		self.text[start..self.pos].parse::<u32>()
+			.map(Some)
+			.map_err(|_| DiceParseError::new(DiceParseErrorKind::NumberTooLarge, start))
+	}//end number()
+}//end impl for Scanner
@@ -1,23 +1,61 @@
-use character_pack::CharacterPack;
-use fltk::{app::{self, App, Receiver, Sender}, enums::{Align, FrameType, Shortcut}, frame::Frame, group::{Group, Pack, Scroll, Tile}, menu::{MenuFlag, SysMenuBar}, prelude::{DisplayExt, GroupExt, MenuExt, WidgetBase, WidgetExt}, text::{TextBuffer, TextDisplay}, window::Window};
-use gurps_reactions::character::Character;
+use std::path::PathBuf;
+use character_pack::{CharacterPack, ModifierLine};
+use npc_pack::NpcPack;
+use party_summary::PartySummary;
+use fltk::{app::{self, App, Receiver, Sender}, button::{Button, CheckButton}, dialog::{self, NativeFileChooser, NativeFileChooserOptions, NativeFileChooserType}, enums::{Align, FrameType, Shortcut}, frame::Frame, group::{Group, Pack, Scroll, Tile}, input::IntInput, menu::{Choice, MenuFlag, SysMenuBar}, prelude::{DisplayExt, GroupExt, InputExt, MenuExt, ValuatorExt, WidgetBase, WidgetExt}, text::{TextBuffer, TextDisplay}, valuator::{Counter, CounterType}, window::Window};
+use gurps_reactions::{character::{Character, Npc, Party, PartyRule, ReactionMod}, dice::{Die, DieFaceError, KeepRule, Roll}, history::RollHistory, influence::{InfluenceRoll, InfluenceSkill}, reaction::{ReactionResult, Situation}};
 
 mod character_pack;
+mod npc_pack;
+mod party_summary;
 
 /// The width in pixels for the main window
-const WINDOW_WIDTH: i32 = 850;
+const WINDOW_WIDTH: i32 = CHARACTER_LIST_WIDTH + REACTION_GROUP_WIDTH + NPC_LIST_WIDTH;
 /// The height in pixels for the main window
-const WINDOW_HEIGHT: i32 = 435;
+const WINDOW_HEIGHT: i32 = 665;
 
 /// The height in pixels for the top menu bar
 const TOP_MENU_HEIGHT: i32 = 35;
 /// The width in pixels for the list of characters. Should be less than WINDOW_WIDTH.
-const CHARACTER_LIST_WIDTH: i32 = 500;
+const CHARACTER_LIST_WIDTH: i32 = 560;
 /// The padding to apply to elements within the character list
 const CHARACTER_LIST_PADDING: i32 = 10;
+/// The width in pixels for the group showing reaction rolls.
+const REACTION_GROUP_WIDTH: i32 = 350;
+/// The width in pixels for the NPC and situation panel.
+const NPC_LIST_WIDTH: i32 = 480;
 /// The FrameType to use for major groups in the main window gui
 const MAIN_GROUP_FRAME: FrameType = FrameType::GtkThinUpBox;
 
+/// The height in pixels of the row of roll controls above the die results
+const RCT_CTRL_HEIGHT: i32 = 25;
+/// The amount of pixels in padding around the row of roll controls
+const RCT_CTRL_PADDING: i32 = 5;
+/// The width in pixels of the button that rolls a reaction
+const ROLL_BTN_WIDTH: i32 = 80;
+/// The choice in the roll target list that rolls for everyone on display
+const ROLL_TARGET_PARTY: &str = "Whole Party";
+/// The choice in the roll mode list that makes a normal reaction roll.
+/// After it, the choices follow the order of InfluenceSkill::ALL.
+const ROLL_MODE_REACTION: &str = "Reaction Roll";
+/// The width in pixels of the choice for how many times to roll
+const ROLL_COUNT_WIDTH: i32 = 90;
+/// The choices for how many times to roll a reaction and which roll to keep
+const ROLL_COUNT_CHOICES: [(&str, usize, KeepRule); 5] = [
+	("Roll Once", 1, KeepRule::Highest),
+	("Best of 2", 2, KeepRule::Highest),
+	("Best of 3", 3, KeepRule::Highest),
+	("Worst of 2", 2, KeepRule::Lowest),
+	("Worst of 3", 3, KeepRule::Lowest),
+];
+/// The width in pixels of the counter for the influence skill level
+const SKILL_LEVEL_WIDTH: i32 = 80;
+/// The width in pixels of the check for entering dice rolled by hand
+const MANUAL_DICE_CHECK_WIDTH: i32 = 110;
+
+/// The filter for the file choosers that save and open characters
+const CHARACTER_FILE_FILTER: &str = "Character Files\t*.json";
+
 /// The width and height in pixels of each frame that shows a die result
 const DIE_FRM_SIZE: i32 = 50;
 /// The amount of pixels in padding to apply to each frame that shows a die result
@@ -25,7 +63,9 @@ const DIE_FRM_PADDING: i32 = 30;
 /// The FrameType to use for the frames that show die results
 const DIE_FRM_FRAME: FrameType = FrameType::GtkThinUpBox;
 /// The alignment to use for the label on each frame that shows a die result
-const DIE_FRM_ALIGN: Align = Align::Top;
+const DIE_FRM_ALIGN: Align = Align::Inside;
+/// The font size of the label for each frame that shows a die result
+const DIE_FRM_LABEL_SIZE: i32 = 18;
 
 /// The width and height in pixels of each frame that shows a reaction result
 const RCT_FRM_SIZE: i32 = 65;
@@ -39,14 +79,24 @@ const RCT_FRM_ALIGN: Align = Align::Inside;
 const RCT_FRM_LABEL_SIZE: i32 = 22;
 /// The number of pixels in padding to apply to the textbox showing detailed reaction roll results.
 const RCT_RST_TXT_PADDING: i32 = 20;
+/// The height in pixels of the label above the roll history
+const HISTORY_LBL_HEIGHT: i32 = 20;
+/// The height in pixels of the textbox showing the roll history
+const HISTORY_TXT_HEIGHT: i32 = 140;
 
 /// Holds all the stuff necessary for showing and interacting with the GUI.
-#[allow(dead_code)]
+#[allow(dead_code, clippy::upper_case_acronyms)]
 pub struct GUI {
 	app: App,
 	ux_main_window: Window,
 	msg_sender: Sender<InterfaceMessage>,
 	msg_receiver: Receiver<InterfaceMessage>,
+	ux_roll_target_choice: Choice,
+	ux_roll_count_choice: Choice,
+	ux_roll_mode_choice: Choice,
+	ux_skill_level: Counter,
+	ux_manual_dice_check: CheckButton,
+	ux_manual_die_inputs: Vec<IntInput>,
 	ux_die_frm_1: Frame,
 	ux_die_frm_2: Frame,
 	ux_die_frm_3: Frame,
@@ -55,8 +105,13 @@ pub struct GUI {
 	ux_rct_frm_sum: Frame,
 	ux_rct_frm_res: Frame,
 	ux_rct_frm_result_txt_box: TextDisplay,
+	ux_history_lbl: Frame,
+	ux_history_txt_box: TextDisplay,
 	ux_char_contain_pack: Pack,
+	ux_party_summary: Option<PartySummary>,
 	ux_char_boxes: Vec<CharacterPack>,
+	ux_npc_contain_pack: Pack,
+	ux_npc_box: Option<NpcPack>,
 }//end struct GUI
 
 impl GUI {
@@ -78,27 +133,315 @@ impl GUI {
 			let char = char_pack.get_character();
 			chars.push(char);
 		}//end getting character from each character pack
-		return chars;
+		chars
 	}//end get_character()
 
+	/// Gets the modifier typed into the add-modifier row
+	/// for the owner of a list of modifiers.
+	pub fn get_new_modifier(&self, owner: ModifierOwner) -> Option<ReactionMod> {
+		match owner {
+			ModifierOwner::Character(char_index) => self.ux_char_boxes.get(char_index).map(|char_pack| char_pack.get_new_modifier()),
+			ModifierOwner::Npc => self.ux_npc_box.as_ref().map(|npc_box| npc_box.ux_new_npc_mod_line.get_modifier()),
+			ModifierOwner::Situation => self.ux_npc_box.as_ref().map(|npc_box| npc_box.ux_new_situation_mod_line.get_modifier()),
+		}//end matching who the modifier is for
+	}//end get_new_modifier()
+
+	/// Gets the NPC as defined by the interface.
+	pub fn get_npc(&self) -> Npc {
+		match &self.ux_npc_box {
+			Some(npc_box) => npc_box.get_npc(),
+			None => Npc::default(),
+		}//end matching whether there's an NPC box
+	}//end get_npc()
+
+	/// Gets the situational modifiers as defined by the interface.
+	pub fn get_situational_modifiers(&self) -> Vec<ReactionMod> {
+		match &self.ux_npc_box {
+			Some(npc_box) => npc_box.get_situational_modifiers(),
+			None => Vec::new(),
+		}//end matching whether there's an NPC box
+	}//end get_situational_modifiers()
+
+	/// Updates the display with the provided NPC and situational modifiers.
+	pub fn set_npc_display(&mut self, npc: &Npc, situational: &[ReactionMod]) {
+		while self.ux_npc_contain_pack.children() > 0 { self.ux_npc_contain_pack.remove_by_index(0); }
+		let mut npc_box = NpcPack::new(npc, situational);
+		self.ux_npc_contain_pack.add(&*npc_box);
+		for (mod_index, mod_line) in npc_box.ux_npc_mod_refs.iter_mut().enumerate() {
+			GUI::emit_modifier_line(self.msg_sender, mod_line, ModifierOwner::Npc, mod_index);
+		}//end adding handlers to each NPC mod_line
+		for (mod_index, mod_line) in npc_box.ux_situation_mod_refs.iter_mut().enumerate() {
+			GUI::emit_modifier_line(self.msg_sender, mod_line, ModifierOwner::Situation, mod_index);
+		}//end adding handlers to each situational mod_line
+		npc_box.ux_new_npc_mod_line.add_btn.emit(self.msg_sender, InterfaceMessage::AddModifier(ModifierOwner::Npc));
+		npc_box.ux_new_situation_mod_line.add_btn.emit(self.msg_sender, InterfaceMessage::AddModifier(ModifierOwner::Situation));
+		self.ux_npc_box = Some(npc_box);
+		// forces the stupid pack to resize and redraw itself
+		self.ux_npc_contain_pack.resize(self.ux_npc_contain_pack.x(), self.ux_npc_contain_pack.y(), self.ux_npc_contain_pack.w(), self.ux_npc_contain_pack.h());
+	}//end set_npc_display()
+
+	/// Sets up the buttons on a modifier line to send messages
+	/// about the modifier at mod_index belonging to owner.
+	fn emit_modifier_line(sender: Sender<InterfaceMessage>, mod_line: &mut ModifierLine, owner: ModifierOwner, mod_index: usize) {
+		mod_line.mod_up_btn.emit(sender, InterfaceMessage::MoveModifierUp(owner, mod_index));
+		mod_line.mod_down_btn.emit(sender, InterfaceMessage::MoveModifierDown(owner, mod_index));
+		mod_line.mod_remove_btn.emit(sender, InterfaceMessage::RemoveModifier(owner, mod_index));
+	}//end emit_modifier_line()
+
+	/// Gets whether each character is involved in the current interaction,
+	/// in the same order as get_characters().
+	pub fn get_involved(&self) -> Vec<bool> {
+		match &self.ux_party_summary {
+			Some(summary) => summary.get_involved(),
+			None => Vec::new(),
+		}//end matching whether there's a party summary
+	}//end get_involved()
+
+	/// Sets whether each character is involved in the current interaction,
+	/// in the same order as get_characters().
+	pub fn set_involved(&mut self, involved: &[bool]) {
+		if let Some(summary) = &mut self.ux_party_summary {
+			for (check, is_involved) in summary.ux_member_checks.iter_mut().zip(involved) {
+				check.set_checked(*is_involved);
+			}//end setting each check
+		}//end if there's a party summary
+		self.update_party_summary();
+	}//end set_involved()
+
+	/// Gets the characters on display as a party, with each
+	/// marked as involved or not based on the party summary.
+	pub fn get_party(&self) -> Party {
+		let characters = self.get_characters();
+		match &self.ux_party_summary {
+			Some(summary) => summary.get_party(&characters),
+			None => Party::from_characters(characters),
+		}//end matching whether there's a party summary
+	}//end get_party()
+
+	/// Gets the rule selected for combining the party's modifiers.
+	pub fn get_party_rule(&self) -> PartyRule {
+		match &self.ux_party_summary {
+			Some(summary) => summary.get_rule(),
+			None => PartyRule::default(),
+		}//end matching whether there's a party summary
+	}//end get_party_rule()
+
+	/// Refreshes the sums and total shown in the party summary.
+	pub fn update_party_summary(&mut self) {
+		let characters = self.get_characters();
+		if let Some(summary) = &mut self.ux_party_summary { summary.update(&characters); }
+	}//end update_party_summary()
+
 	/// Updates the display with the provided characters.
-	pub fn set_character_display(&mut self, characters: &Vec<Character>) {
+	pub fn set_character_display(&mut self, characters: &[Character]) {
+		// remember the state of the party summary before clearing it
+		let involved = self.get_involved();
+		let party_rule = match &self.ux_party_summary {
+			Some(summary) => summary.get_rule(),
+			None => PartyRule::default(),
+		};
 		// clears any current children from the pack
 		while self.ux_char_contain_pack.children() > 0 { self.ux_char_contain_pack.remove_by_index(0); }
 		self.ux_char_boxes.clear();
+		// add the summary of the whole party at the top
+		let mut party_summary = PartySummary::new(characters, &involved, party_rule);
+		self.ux_char_contain_pack.add(&*party_summary);
+		for member_check in party_summary.ux_member_checks.iter_mut() {
+			member_check.emit(self.msg_sender, InterfaceMessage::EditParty);
+		}//end adding handlers to each party member check
+		party_summary.ux_rule_choice.emit(self.msg_sender, InterfaceMessage::EditParty);
+		self.ux_party_summary = Some(party_summary);
 		// add character boxes for each character
-		for character in characters {
+		for (char_index, character) in characters.iter().enumerate() {
 			let mut char_box = character_pack::CharacterPack::new(character);
 			self.ux_char_contain_pack.add(&*char_box);
-			for mod_line in char_box.ux_mod_refs.iter_mut() {
+			for (mod_index, mod_line) in char_box.ux_mod_refs.iter_mut().enumerate() {
 				mod_line.mod_value.emit(app::channel().0, InterfaceMessage::EditCharacter);
+				GUI::emit_modifier_line(self.msg_sender, mod_line, ModifierOwner::Character(char_index), mod_index);
 			}//end adding handlers to each mod_line in char_box
+			char_box.ux_new_mod_line.add_btn.emit(self.msg_sender, InterfaceMessage::AddModifier(ModifierOwner::Character(char_index)));
+			char_box.ux_up_btn.emit(self.msg_sender, InterfaceMessage::MoveCharacterUp(char_index));
+			char_box.ux_down_btn.emit(self.msg_sender, InterfaceMessage::MoveCharacterDown(char_index));
+			char_box.ux_duplicate_btn.emit(self.msg_sender, InterfaceMessage::DuplicateCharacter(char_index));
+			char_box.ux_remove_btn.emit(self.msg_sender, InterfaceMessage::RemoveCharacter(char_index));
 			self.ux_char_boxes.push(char_box);
 		}//end looping for each character
 		// forces the stupid pack to resize and redraw itself
 		self.ux_char_contain_pack.resize(self.ux_char_contain_pack.x(), self.ux_char_contain_pack.y(), self.ux_char_contain_pack.w(), self.ux_char_contain_pack.h());
+		self.set_roll_targets(characters);
 	}//end set_character_display()
 
+	/// Asks the user where to save a character file.  
+	/// Returns None if they cancel.
+	pub fn choose_save_path(&self) -> Option<PathBuf> {
+		let mut chooser = NativeFileChooser::new(NativeFileChooserType::BrowseSaveFile);
+		chooser.set_title("Save Characters As");
+		chooser.set_filter(CHARACTER_FILE_FILTER);
+		chooser.set_option(NativeFileChooserOptions::SaveAsConfirm);
+		chooser.set_preset_file("characters.json");
+		GUI::run_file_chooser(chooser)
+	}//end choose_save_path()
+
+	/// Asks the user which character file to open.  
+	/// Returns None if they cancel.
+	pub fn choose_open_path(&self) -> Option<PathBuf> {
+		let mut chooser = NativeFileChooser::new(NativeFileChooserType::BrowseFile);
+		chooser.set_title("Open Characters");
+		chooser.set_filter(CHARACTER_FILE_FILTER);
+		GUI::run_file_chooser(chooser)
+	}//end choose_open_path()
+
+	/// Shows the file chooser and gets the chosen path, if any.
+	fn run_file_chooser(mut chooser: NativeFileChooser) -> Option<PathBuf> {
+		chooser.show();
+		if let Some(err) = chooser.error_message() {
+			if err != "No error" { GUI::show_error(&err); }
+		}//end if the chooser had a problem
+		let path = chooser.filename();
+		if path.as_os_str().is_empty() {None} else {Some(path)}
+	}//end run_file_chooser()
+
+	/// Shows an error message to the user in a dialog.
+	pub fn show_error(message: &str) {
+		dialog::alert_default(message);
+	}//end show_error()
+
+	/// Updates the list of things to roll for, keeping the
+	/// current selection if it's still around.
+	fn set_roll_targets(&mut self, characters: &[Character]) {
+		let selected = self.ux_roll_target_choice.value();
+		self.ux_roll_target_choice.clear();
+		self.ux_roll_target_choice.add_choice(ROLL_TARGET_PARTY);
+		for character in characters {
+			let name = if character.name.is_empty() {"(unnamed)"} else {&character.name};
+			self.ux_roll_target_choice.add(&escape_menu_label(name), Shortcut::None, MenuFlag::Normal, |_| {});
+		}//end adding a choice for each character
+		if selected > 0 && (selected as usize) <= characters.len() { self.ux_roll_target_choice.set_value(selected); }
+		else { self.ux_roll_target_choice.set_value(0); }
+	}//end set_roll_targets()
+
+	/// Gets the index of the character selected to roll for,
+	/// or None if the whole party is selected.
+	pub fn get_roll_target(&self) -> Option<usize> {
+		match self.ux_roll_target_choice.value() {
+			value if value > 0 => Some(value as usize - 1),
+			_ => None,
+		}//end matching selected choice
+	}//end get_roll_target()
+
+	/// Selects the character at the index to roll for, or
+	/// the whole party if None or the index is out of range.
+	pub fn set_roll_target(&mut self, target: Option<usize>) {
+		let value = match target {
+			Some(index) if index < self.ux_char_boxes.len() => index as i32 + 1,
+			_ => 0,
+		};
+		self.ux_roll_target_choice.set_value(value);
+	}//end set_roll_target()
+
+	/// Gets how many times to roll a reaction, and which of the rolls to keep.
+	pub fn get_roll_count(&self) -> (usize, KeepRule) {
+		let index = self.ux_roll_count_choice.value().max(0) as usize;
+		let (_, times, keep) = ROLL_COUNT_CHOICES.get(index).copied().unwrap_or(ROLL_COUNT_CHOICES[0]);
+		(times, keep)
+	}//end get_roll_count()
+
+	/// Gets the influence skill to roll in place of a reaction roll,
+	/// along with the skill level, or None for a normal reaction roll.
+	pub fn get_influence(&self) -> Option<(InfluenceSkill, i32)> {
+		match self.ux_roll_mode_choice.value() {
+			value if value > 0 => InfluenceSkill::ALL.get(value as usize - 1)
+				.map(|skill| (*skill, self.ux_skill_level.value().round() as i32)),
+			_ => None,
+		}//end matching selected choice
+	}//end get_influence()
+
+	/// Gets the dice typed in by hand, or None if the dice should be rolled instead.  
+	/// Anything that isn't a face from 1 to 6 gives an error.
+	pub fn get_manual_dice(&self) -> Option<Result<Vec<Die>, DieFaceError>> {
+		if !self.ux_manual_dice_check.is_checked() { return None; }
+		let faces: Vec<i32> = self.ux_manual_die_inputs.iter()
+			.map(|die_input| die_input.value().trim().parse().unwrap_or(0))
+			.collect();
+		Some(Roll::from_faces(&faces, 0).map(|roll| roll.dice))
+	}//end get_manual_dice()
+
+	/// Updates the dice, reaction, and result displays to show an influence roll.  
+	/// If the NPC got a normal reaction roll instead, that's shown along with it.
+	pub fn set_influence_display(&mut self, influence: &InfluenceRoll, fallback: Option<&ReactionResult>) {
+		let mut result_text = format!("{}\n{}\n\n", influence, influence.skill.notes());
+		if self.ux_manual_dice_check.is_checked() && (influence.resistance.is_some() || fallback.is_some()) {
+			result_text.push_str("Only the skill roll used the physical dice. Any Will roll or normal reaction roll was made by the program.\n\n");
+		}//end if some of the rolls weren't the physical dice
+		match (influence.outcomes(), fallback) {
+			(Some(outcomes), _) => {
+				self.set_dice_display(&influence.roll.roll);
+				self.ux_rct_frm_roll.set_label(&influence.roll.total().to_string());
+				self.ux_rct_frm_mod.set_label(&format!("vs {}", influence.roll.target));
+				self.ux_rct_frm_sum.set_label(&format!("{:+}", influence.roll.margin()));
+				if let Some(level) = influence.level() { self.ux_rct_frm_res.set_label(level.name()); }
+				let outcome_lines: Vec<String> = outcomes.iter().map(|outcome| outcome.to_string()).collect();
+				result_text.push_str(&outcome_lines.join("\n"));
+			},
+			(None, Some(result)) => {
+				self.set_roll_display(result);
+				result_text.push_str("Rolled a normal reaction instead:\n");
+				result_text.push_str(&reaction_result_text(result));
+			},
+			(None, None) => result_text.push_str("Roll a normal reaction instead."),
+		}//end matching whether the influence roll decided the reaction
+		let mut result_buf = self.ux_rct_frm_result_txt_box.buffer().unwrap_or_default();
+		result_buf.set_text(&result_text);
+		self.ux_rct_frm_result_txt_box.set_buffer(result_buf);
+		self.ux_main_window.redraw();
+	}//end set_influence_display()
+
+	/// Updates the die frames to show the dice of a roll.
+	fn set_dice_display(&mut self, roll: &Roll) {
+		let die_frames = [&mut self.ux_die_frm_1, &mut self.ux_die_frm_2, &mut self.ux_die_frm_3];
+		for (index, die_frame) in die_frames.into_iter().enumerate() {
+			match roll.dice.get(index) {
+				Some(die) => die_frame.set_label(&die.result.to_string()),
+				None => die_frame.set_label("-"),
+			}//end matching whether there's a die for this frame
+		}//end updating each die frame
+	}//end set_dice_display()
+
+	/// Updates the dice, reaction, and result displays to show a roll.
+	pub fn set_roll_display(&mut self, result: &ReactionResult) {
+		let roll = &result.roll;
+		self.set_dice_display(roll);
+		self.ux_rct_frm_roll.set_label(&roll.dice_sum().to_string());
+		self.ux_rct_frm_mod.set_label(&format!("{:+}", roll.modifier));
+		self.ux_rct_frm_sum.set_label(&roll.total.to_string());
+		self.ux_rct_frm_res.set_label(result.level().name());
+
+		let result_text = reaction_result_text(result);
+		let mut result_buf = self.ux_rct_frm_result_txt_box.buffer().unwrap_or_default();
+		result_buf.set_text(&result_text);
+		self.ux_rct_frm_result_txt_box.set_buffer(result_buf);
+		self.ux_main_window.redraw();
+	}//end set_roll_display()
+
+	/// Updates the history panel to show past rolls, newest first.  
+	/// If a character is given, only their rolls are shown.
+	pub fn set_history_display(&mut self, history: &RollHistory, character: Option<&str>) {
+		let entry_lines: Vec<String> = match character {
+			Some(character) => {
+				self.ux_history_lbl.set_label(&format!("Roll History: {}", character));
+				history.for_character(character).rev().map(|entry| entry.to_string()).collect()
+			},
+			None => {
+				self.ux_history_lbl.set_label("Roll History");
+				history.entries.iter().rev().map(|entry| entry.to_string()).collect()
+			},
+		};
+		let mut history_buf = self.ux_history_txt_box.buffer().unwrap_or_default();
+		history_buf.set_text(&entry_lines.join("\n"));
+		self.ux_history_txt_box.set_buffer(history_buf);
+		self.ux_main_window.redraw();
+	}//end set_history_display()
+
 	/// Something like the init-components of other systems.  
 	/// Sets up all the widgets and stuff for the GUI.
 	pub fn initialize() -> GUI {
@@ -143,42 +486,131 @@ impl GUI {
 			s,
 			InterfaceMessage::OpenCharacter
 		);
+		top_menu.add_emit(
+			"Reaction/Roll\t",
+			Shortcut::Ctrl | 'r',
+			MenuFlag::Normal,
+			s,
+			InterfaceMessage::Roll
+		);
 
 		// group for listing reaction rolls
 		let mut reaction_roll_group = Group::default()
 			.with_pos(CHARACTER_LIST_WIDTH, TOP_MENU_HEIGHT)
-			.with_size(WINDOW_WIDTH - CHARACTER_LIST_WIDTH, tiles.height());
+			.with_size(REACTION_GROUP_WIDTH, tiles.height());
 		reaction_roll_group.set_frame(MAIN_GROUP_FRAME);
 		tiles.add(&reaction_roll_group);
 
+		let mut roll_target_choice = Choice::default()
+			.with_pos(reaction_roll_group.x() + RCT_CTRL_PADDING, TOP_MENU_HEIGHT + RCT_CTRL_PADDING)
+			.with_size(reaction_roll_group.width() - ROLL_COUNT_WIDTH - ROLL_BTN_WIDTH - (RCT_CTRL_PADDING * 4), RCT_CTRL_HEIGHT);
+		roll_target_choice.add_choice(ROLL_TARGET_PARTY);
+		roll_target_choice.set_value(0);
+		roll_target_choice.set_tooltip("Who to roll the reaction for");
+		reaction_roll_group.add(&roll_target_choice);
+
+		let mut roll_count_choice = Choice::default()
+			.with_pos(roll_target_choice.x() + roll_target_choice.width() + RCT_CTRL_PADDING, roll_target_choice.y())
+			.with_size(ROLL_COUNT_WIDTH, RCT_CTRL_HEIGHT);
+		for (label, _, _) in ROLL_COUNT_CHOICES {
+			roll_count_choice.add_choice(label);
+		}//end adding each way to roll as a choice
+		roll_count_choice.set_value(0);
+		roll_count_choice.set_tooltip("Roll several times and keep one, as with Luck or Cursed");
+		reaction_roll_group.add(&roll_count_choice);
+
+		let mut roll_btn = Button::default()
+			.with_pos(roll_count_choice.x() + roll_count_choice.width() + RCT_CTRL_PADDING, roll_target_choice.y())
+			.with_size(ROLL_BTN_WIDTH, RCT_CTRL_HEIGHT)
+			.with_label("Roll");
+		roll_btn.emit(s, InterfaceMessage::Roll);
+		reaction_roll_group.add(&roll_btn);
+
+		let mut roll_mode_choice = Choice::default()
+			.with_pos(roll_target_choice.x(), roll_target_choice.y() + roll_target_choice.height() + RCT_CTRL_PADDING)
+			.with_size(reaction_roll_group.width() - SKILL_LEVEL_WIDTH - (RCT_CTRL_PADDING * 3), RCT_CTRL_HEIGHT);
+		roll_mode_choice.add_choice(ROLL_MODE_REACTION);
+		for skill in InfluenceSkill::ALL {
+			roll_mode_choice.add_choice(&format!("Influence: {}", skill));
+		}//end adding each influence skill as a choice
+		roll_mode_choice.set_value(0);
+		roll_mode_choice.set_tooltip("Roll a reaction, or roll an influence skill in place of one (B359)");
+		reaction_roll_group.add(&roll_mode_choice);
+
+		let mut skill_level = Counter::default()
+			.with_pos(roll_mode_choice.x() + roll_mode_choice.width() + RCT_CTRL_PADDING, roll_mode_choice.y())
+			.with_size(SKILL_LEVEL_WIDTH, RCT_CTRL_HEIGHT)
+			.with_type(CounterType::Simple);
+		skill_level.set_bounds(3., 30.);
+		skill_level.set_step(1., 1);
+		skill_level.set_value(12.);
+		skill_level.set_tooltip("Effective level of the influence skill");
+		reaction_roll_group.add(&skill_level);
+
+		let mut manual_dice_check = CheckButton::default()
+			.with_pos(roll_mode_choice.x(), roll_mode_choice.y() + roll_mode_choice.height() + RCT_CTRL_PADDING)
+			.with_size(MANUAL_DICE_CHECK_WIDTH, RCT_CTRL_HEIGHT)
+			.with_label("Physical Dice");
+		manual_dice_check.set_tooltip("Use the dice typed in to the right instead of rolling them");
+		manual_dice_check.clear_visible_focus();
+		manual_dice_check.set_callback({
+			let mut roll_count_choice = roll_count_choice.clone();
+			// physical dice are only rolled once, so there's nothing to keep
+			move |manual_dice_check| {
+				if manual_dice_check.is_checked() { roll_count_choice.deactivate(); }
+				else { roll_count_choice.activate(); }
+			}//end closure
+		});
+		reaction_roll_group.add(&manual_dice_check);
+
+		let manual_die_width = (reaction_roll_group.width() - MANUAL_DICE_CHECK_WIDTH - (RCT_CTRL_PADDING * 5)) / 3;
+		let mut manual_die_inputs = Vec::new();
+		for die_num in 0..3 {
+			let mut die_input = IntInput::default()
+				.with_pos(manual_dice_check.x() + manual_dice_check.width() + RCT_CTRL_PADDING + (die_num * (manual_die_width + RCT_CTRL_PADDING)), manual_dice_check.y())
+				.with_size(manual_die_width, RCT_CTRL_HEIGHT);
+			die_input.set_maximum_size(1);
+			die_input.set_tooltip(&format!("die {} as rolled, from 1 to 6", die_num + 1));
+			die_input.set_tab_nav(true);
+			reaction_roll_group.add(&die_input);
+			manual_die_inputs.push(die_input);
+		}//end adding an input for each die
+
+		let die_frm_y = manual_dice_check.y() + manual_dice_check.height() + DIE_FRM_PADDING;
 		let mut die_frm_2 = Frame::default()
-			.with_pos(reaction_roll_group.x() + (reaction_roll_group.width() / 2) - (DIE_FRM_SIZE / 2),TOP_MENU_HEIGHT + DIE_FRM_PADDING)
+			.with_pos(reaction_roll_group.x() + (reaction_roll_group.width() / 2) - (DIE_FRM_SIZE / 2), die_frm_y)
 			.with_size(DIE_FRM_SIZE,DIE_FRM_SIZE)
-			.with_label("die 2")
+			.with_label("-")
 			.with_align(DIE_FRM_ALIGN);
 		die_frm_2.set_frame(DIE_FRM_FRAME);
+		die_frm_2.set_label_size(DIE_FRM_LABEL_SIZE);
+		die_frm_2.set_tooltip("die 2");
 		reaction_roll_group.add(&die_frm_2);
 
 		let mut die_frm_1 = Frame::default()
-			.with_pos(reaction_roll_group.x() + ((die_frm_2.x() - reaction_roll_group.x()) / 2) - (DIE_FRM_SIZE / 2), TOP_MENU_HEIGHT + DIE_FRM_PADDING)
+			.with_pos(reaction_roll_group.x() + ((die_frm_2.x() - reaction_roll_group.x()) / 2) - (DIE_FRM_SIZE / 2), die_frm_y)
 			.with_size(DIE_FRM_SIZE,DIE_FRM_SIZE)
-			.with_label("die 1")
+			.with_label("-")
 			.with_align(DIE_FRM_ALIGN);
 		die_frm_1.set_frame(DIE_FRM_FRAME);
+		die_frm_1.set_label_size(DIE_FRM_LABEL_SIZE);
+		die_frm_1.set_tooltip("die 1");
 		reaction_roll_group.add(&die_frm_1);
 
 		let mut die_frm_3 = Frame::default()
-			.with_pos(die_frm_2.x() + die_frm_2.width() + ( (reaction_roll_group.x() + reaction_roll_group.width() - die_frm_2.x() - die_frm_2.width()) / 2) - (DIE_FRM_SIZE / 2), TOP_MENU_HEIGHT + DIE_FRM_PADDING)
+			.with_pos(die_frm_2.x() + die_frm_2.width() + ( (reaction_roll_group.x() + reaction_roll_group.width() - die_frm_2.x() - die_frm_2.width()) / 2) - (DIE_FRM_SIZE / 2), die_frm_y)
 			.with_size(DIE_FRM_SIZE,DIE_FRM_SIZE)
-			.with_label("die 3")
+			.with_label("-")
 			.with_align(DIE_FRM_ALIGN);
 		die_frm_3.set_frame(DIE_FRM_FRAME);
+		die_frm_3.set_label_size(DIE_FRM_LABEL_SIZE);
+		die_frm_3.set_tooltip("die 3");
 		reaction_roll_group.add(&die_frm_3);
 
 		let mut rct_frm_mod = Frame::default()
 			.with_pos(reaction_roll_group.x() + (reaction_roll_group.width() / 2) - (RCT_FRM_SIZE / 2), die_frm_2.y() + die_frm_2.height() + DIE_FRM_PADDING.max(RCT_FRM_PADDING))
 			.with_size(RCT_FRM_SIZE,RCT_FRM_SIZE)
-			.with_label("8")
+			.with_label("-")
 			.with_align(RCT_FRM_ALIGN);
 		rct_frm_mod.set_frame(RCT_FRM_FRAME);
 		rct_frm_mod.set_label_size(RCT_FRM_LABEL_SIZE);
@@ -187,7 +619,7 @@ impl GUI {
 		let mut rct_frm_roll = Frame::default()
 			.with_pos(reaction_roll_group.x() + ((rct_frm_mod.x() - reaction_roll_group.x()) / 2) - (RCT_FRM_SIZE / 2), rct_frm_mod.y())
 			.with_size(RCT_FRM_SIZE,RCT_FRM_SIZE)
-			.with_label("6")
+			.with_label("-")
 			.with_align(RCT_FRM_ALIGN);
 		rct_frm_roll.set_frame(RCT_FRM_FRAME);
 		rct_frm_roll.set_label_size(RCT_FRM_LABEL_SIZE);
@@ -196,7 +628,7 @@ impl GUI {
 		let mut rct_frm_sum = Frame::default()
 			.with_pos(rct_frm_mod.x() + rct_frm_mod.width() + ( (reaction_roll_group.x() + reaction_roll_group.width() - rct_frm_mod.x() - rct_frm_mod.width()) / 2) - (RCT_FRM_SIZE / 2), rct_frm_mod.y())
 			.with_size(RCT_FRM_SIZE,RCT_FRM_SIZE)
-			.with_label("14")
+			.with_label("-")
 			.with_align(RCT_FRM_ALIGN);
 		rct_frm_sum.set_frame(RCT_FRM_FRAME);
 		rct_frm_sum.set_label_size(RCT_FRM_LABEL_SIZE);
@@ -229,7 +661,7 @@ impl GUI {
 		let mut rct_frm_result = Frame::default()
 			.with_pos(reaction_roll_group.x() + (reaction_roll_group.width() / 2) - (RCT_FRM_SIZE), rct_nxt.y() + rct_nxt.height())
 			.with_size(RCT_FRM_SIZE * 2, RCT_FRM_SIZE / 2)
-			.with_label("Good")
+			.with_label("-")
 			.with_align(RCT_FRM_ALIGN);
 		rct_frm_result.set_frame(RCT_FRM_FRAME);
 		reaction_roll_group.add(&rct_frm_result);
@@ -237,11 +669,25 @@ impl GUI {
 		let mut rct_result_txt_buf = TextBuffer::default();
 		let mut rct_result_txt_box = TextDisplay::default()
 			.with_pos(reaction_roll_group.x() + (RCT_FRM_PADDING / 2), rct_frm_result.y() + rct_frm_result.height() + (RCT_RST_TXT_PADDING / 2))
-			.with_size(reaction_roll_group.width() - RCT_FRM_PADDING, reaction_roll_group.height() - rct_nxt.y() - rct_nxt.height() - RCT_RST_TXT_PADDING);
+			.with_size(reaction_roll_group.width() - RCT_FRM_PADDING, reaction_roll_group.height() - rct_nxt.y() - rct_nxt.height() - RCT_RST_TXT_PADDING - HISTORY_LBL_HEIGHT - HISTORY_TXT_HEIGHT);
 		rct_result_txt_buf.set_text("General Reaction: ...\nPotential Combat: ...\nCommercial Transactions: ...\nRequests for Aid: ...\nRequests for Info: ...\nLoyalty: ...");
 		rct_result_txt_box.set_buffer(rct_result_txt_buf);
 		reaction_roll_group.add_resizable(&rct_result_txt_box);
 
+		let history_lbl = Frame::default()
+			.with_pos(rct_result_txt_box.x(), rct_result_txt_box.y() + rct_result_txt_box.height())
+			.with_size(rct_result_txt_box.width(), HISTORY_LBL_HEIGHT)
+			.with_label("Roll History")
+			.with_align(Align::Left.union(Align::Inside));
+		reaction_roll_group.add(&history_lbl);
+
+		let mut history_txt_box = TextDisplay::default()
+			.with_pos(history_lbl.x(), history_lbl.y() + history_lbl.height())
+			.with_size(history_lbl.width(), HISTORY_TXT_HEIGHT);
+		history_txt_box.set_buffer(TextBuffer::default());
+		history_txt_box.set_tooltip("Past rolls, newest first");
+		reaction_roll_group.add(&history_txt_box);
+
 		// group for listing characters
 		let mut characters_scroll = Scroll::default()
 			.with_pos(0,TOP_MENU_HEIGHT)
@@ -255,48 +701,37 @@ impl GUI {
 		character_pack.set_spacing(CHARACTER_LIST_PADDING);
 		character_pack.set_frame(FrameType::EmbossedFrame);
 		characters_scroll.add_resizable(&character_pack);
-		character_pack.resize_callback({
-			move |pack,_,_,_,_| {
-				match pack.parent() {
-					None => {},
-					Some(parent) => {
-						let px = parent.x();
-						let py = parent.y();
-						let pw = parent.w();
-						let ph = parent.h();
-						let ex = px + CHARACTER_LIST_PADDING;
-						let ey = py + CHARACTER_LIST_PADDING;
-						let ew = pw - (2 * CHARACTER_LIST_PADDING);
-						let eh = ph - (2 * CHARACTER_LIST_PADDING);
-						if pack.x() != ex || pack.y() != ey {pack.set_pos(ex,ey);}
-						if pack.w() != ew || pack.h() != eh {pack.set_size(ew,eh);}
-					},
-				}//end matching whether we can access the parent
-			}//end closure
-		});
-
-		let mut ch1 = Frame::default()
-			.with_size(0,50)
-			.with_label("Character 1");
-		ch1.set_frame(FrameType::GtkRoundUpFrame);
-		character_pack.add(&ch1);
-
-		let mut ch2 = Frame::default()
-			.with_size(0,50)
-			.with_label("Character 2");
-		ch2.set_frame(FrameType::GtkRoundUpFrame);
-		character_pack.add(&ch2);
+		character_pack.resize_callback(fit_pack_to_parent);
+
+		// group for the NPC and situation
+		let mut npc_scroll = Scroll::default()
+			.with_pos(CHARACTER_LIST_WIDTH + REACTION_GROUP_WIDTH, TOP_MENU_HEIGHT)
+			.with_size(NPC_LIST_WIDTH, tiles.height());
+		npc_scroll.set_frame(MAIN_GROUP_FRAME);
+		tiles.add(&npc_scroll);
+
+		let mut npc_pack = Pack::default()
+			.with_pos(npc_scroll.x() + CHARACTER_LIST_PADDING, npc_scroll.y() + CHARACTER_LIST_PADDING)
+			.with_size(npc_scroll.width() - (CHARACTER_LIST_PADDING * 2), 0);
+		npc_pack.set_spacing(CHARACTER_LIST_PADDING);
+		npc_pack.set_frame(FrameType::EmbossedFrame);
+		npc_scroll.add_resizable(&npc_pack);
+		npc_pack.resize_callback(fit_pack_to_parent);
 
-		// TODO: Just add raw boxes of stuff for each character
-		// TODO: Also add a box at the top with summed modifiers for the whole party, maybe with checkboxes and sums for each character
 		// TODO: Also Also think about how to store the list of all current characters. Probably need to keep list of current characters and checks somehow
 
 		main_window.show();
-		GUI {
+		let mut gui = GUI {
 			app: reaction_app,
 			ux_main_window: main_window,
 			msg_sender: s,
 			msg_receiver: r,
+			ux_roll_target_choice: roll_target_choice,
+			ux_roll_count_choice: roll_count_choice,
+			ux_roll_mode_choice: roll_mode_choice,
+			ux_skill_level: skill_level,
+			ux_manual_dice_check: manual_dice_check,
+			ux_manual_die_inputs: manual_die_inputs,
 			ux_die_frm_1: die_frm_1,
 			ux_die_frm_2: die_frm_2,
 			ux_die_frm_3: die_frm_3,
@@ -305,9 +740,17 @@ impl GUI {
 			ux_rct_frm_sum: rct_frm_sum,
 			ux_rct_frm_res: rct_frm_result,
 			ux_rct_frm_result_txt_box: rct_result_txt_box,
+			ux_history_lbl: history_lbl,
+			ux_history_txt_box: history_txt_box,
 			ux_char_contain_pack: character_pack,
+			ux_party_summary: None,
 			ux_char_boxes: Vec::new(),
-		}//end struct construction
+			ux_npc_contain_pack: npc_pack,
+			ux_npc_box: None,
+		};
+		gui.set_character_display(&[]);
+		gui.set_npc_display(&Npc::default(), &[]);
+		gui
 	}//end initialize()
 }//end impl for GUI
 
@@ -315,12 +758,93 @@ impl GUI {
 /// the main function.
 #[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord)]
 pub enum InterfaceMessage {
-	/// Indicates that the user wants to create a new character
+	/// Indicates that the user wants to add a new blank character
 	NewCharacter,
+	/// Indicates that the user wants to remove the character at the index
+	RemoveCharacter(usize),
+	/// Indicates that the user wants to copy the character at the index
+	DuplicateCharacter(usize),
+	/// Indicates that the user wants to move the character at the index up
+	MoveCharacterUp(usize),
+	/// Indicates that the user wants to move the character at the index down
+	MoveCharacterDown(usize),
 	/// Indicates that the user wants to save a character file
 	SaveCharacterAs,
 	/// Indicates that the user wants to open a character file
 	OpenCharacter,
 	/// Indicates that the user has edited a character
 	EditCharacter,
+	/// Indicates that the user has changed who is involved
+	/// or how the party's modifiers are combined
+	EditParty,
+	/// Indicates that the user wants to roll a reaction
+	Roll,
+	/// Indicates that the user wants to add the new modifier
+	/// to the list belonging to the owner
+	AddModifier(ModifierOwner),
+	/// Indicates that the user wants to remove the modifier at the index
+	RemoveModifier(ModifierOwner, usize),
+	/// Indicates that the user wants to move the modifier at the index up
+	MoveModifierUp(ModifierOwner, usize),
+	/// Indicates that the user wants to move the modifier at the index down
+	MoveModifierDown(ModifierOwner, usize),
 }//end enum InterfaceMessage
+
+/// Identifies which list of modifiers in the GUI a message is about.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord)]
+pub enum ModifierOwner {
+	/// The modifiers of the character at the index
+	Character(usize),
+	/// The modifiers of the NPC
+	Npc,
+	/// The situational modifiers
+	Situation,
+}//end enum ModifierOwner
+
+/// Keeps a pack filling its parent, minus some padding.  
+/// Meant to be used as a resize callback.
+fn fit_pack_to_parent(pack: &mut Pack, _x: i32, _y: i32, _w: i32, _h: i32) {
+	match pack.parent() {
+		None => {},
+		Some(parent) => {
+			let px = parent.x();
+			let py = parent.y();
+			let pw = parent.w();
+			let ph = parent.h();
+			let ex = px + CHARACTER_LIST_PADDING;
+			let ey = py + CHARACTER_LIST_PADDING;
+			let ew = pw - (2 * CHARACTER_LIST_PADDING);
+			let eh = ph - (2 * CHARACTER_LIST_PADDING);
+			if pack.x() != ex || pack.y() != ey {pack.set_pos(ex,ey);}
+			if pack.w() != ew || pack.h() != eh {pack.set_size(ew,eh);}
+		},
+	}//end matching whether we can access the parent
+}//end fit_pack_to_parent()
+
+/// Gets the text describing a reaction roll, with the outcome in each
+/// situation and the modifiers that went into it.
+fn reaction_result_text(result: &ReactionResult) -> String {
+	let result_lines: Vec<String> = result.reactions.iter().map(|reaction| reaction.to_string()).collect();
+	let mut result_text = result_lines.join("\n");
+	let general = result.reaction(Situation::General);
+	if let Some(general) = general {
+		result_text.push_str(&format!("\n\nModifiers:\n{}", general.breakdown));
+	}//end if there's a general reaction to list modifiers for
+	for reaction in result.reactions.iter() {
+		if general.is_some_and(|general| general.breakdown == reaction.breakdown) { continue; }
+		result_text.push_str(&format!("\n\n{} Modifiers:\n{}", reaction.situation, reaction.breakdown));
+	}//end listing modifiers for situations that differ from a general reaction
+	if !result.discarded.is_empty() {
+		result_text.push_str(&format!("\n\nKept Roll: {}\nDiscarded Rolls:", result.roll));
+		for roll in result.discarded.iter() {
+			result_text.push_str(&format!("\n{}", roll));
+		}//end listing each discarded roll
+	}//end if any rolls were thrown away
+	result_text
+}//end reaction_result_text()
+
+/// Escapes characters that FLTK menus would otherwise treat
+/// as submenus or shortcuts.
+fn escape_menu_label(label: &str) -> String {
+	label.replace('\\', "\\\\").replace('/', "\\/").replace('&', "&&")
+}//end escape_menu_label()
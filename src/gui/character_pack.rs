@@ -1,13 +1,21 @@
 use std::{cell::RefCell, rc::Rc};
-use fltk::{button::CheckButton, enums::{Align, Event, FrameType}, frame::Frame, group::{Flex, FlexType, Pack}, input::Input, prelude::{GroupExt, InputExt, ValuatorExt, WidgetBase, WidgetExt}, valuator::{Counter, CounterType}, widget_extends};
-use gurps_reactions::character::{Character, ReactionMod};
+use fltk::{button::{Button, CheckButton}, enums::{Align, CallbackTrigger, Event, FrameType}, frame::Frame, group::{Flex, FlexType, Pack}, input::Input, menu::Choice, misc::InputChoice, prelude::{GroupExt, InputExt, MenuExt, ValuatorExt, WidgetBase, WidgetExt}, valuator::{Counter, CounterType}, widget_extends};
+use gurps_reactions::{audience, character::{Character, ReactionMod, Recognition}, reaction::Situation, traits::{self, TraitInfo}};
+
+use super::escape_menu_label;
 
 /// A gui widget that displays and allows editing of a single character
 pub struct CharacterPack {
 	pack: Pack,
 	// pub ux_title_ref: Rc<RefCell<Frame>>,
 	pub ux_char_name_box: Input,
+	pub ux_status: Counter,
+	pub ux_up_btn: Button,
+	pub ux_down_btn: Button,
+	pub ux_duplicate_btn: Button,
+	pub ux_remove_btn: Button,
 	pub ux_mod_refs: Vec<ModifierLine>,
+	pub ux_new_mod_line: NewModifierLine,
 }//end struct CharacterPack
 
 impl CharacterPack {
@@ -20,21 +28,58 @@ impl CharacterPack {
 		Character {
 			name: self.ux_char_name_box.value(),
 			reaction_modifiers: modifiers,
+			status: self.ux_status.value().round() as i32,
 		}//end struct construction
 	}//end get_character()
 
+	/// Gets the modifier that has been typed into the row for adding a new modifier.
+	pub fn get_new_modifier(&self) -> ReactionMod { self.ux_new_mod_line.get_modifier() }
+
 	/// Creates a new character pack out of the provided Character
 	pub fn new(character: &Character) -> CharacterPack {
 		let mut pack = Pack::default();
 		pack.set_spacing(2);
 
-		// add the title that auto-updates
-		let mut char_title = Frame::default()
+		// add the title that auto-updates, along with buttons for the whole character
+		let mut char_title_flex = Flex::default()
 			.with_size(0, 30)
+			.with_type(FlexType::Row);
+		char_title_flex.set_margin(0);
+		let mut char_title = Frame::default()
 			.with_label(&format!("{}\t{}", character.name, character.reaction_sum(false)))
 			.with_align(Align::Inside.union(Align::Center));
 		char_title.set_frame(FrameType::FlatBox);
-		pack.add(&char_title);
+		char_title_flex.add(&char_title);
+
+		let mut up_btn = Button::default()
+			.with_label("@8->");
+		up_btn.set_tooltip("Move character up");
+		up_btn.clear_visible_focus();
+		char_title_flex.add(&up_btn);
+		char_title_flex.fixed(&up_btn, MOD_BTN_WIDTH);
+
+		let mut down_btn = Button::default()
+			.with_label("@2->");
+		down_btn.set_tooltip("Move character down");
+		down_btn.clear_visible_focus();
+		char_title_flex.add(&down_btn);
+		char_title_flex.fixed(&down_btn, MOD_BTN_WIDTH);
+
+		let mut duplicate_btn = Button::default()
+			.with_label("@+");
+		duplicate_btn.set_tooltip("Duplicate character");
+		duplicate_btn.clear_visible_focus();
+		char_title_flex.add(&duplicate_btn);
+		char_title_flex.fixed(&duplicate_btn, MOD_BTN_WIDTH);
+
+		let mut remove_btn = Button::default()
+			.with_label("X");
+		remove_btn.set_tooltip("Remove character");
+		remove_btn.clear_visible_focus();
+		char_title_flex.add(&remove_btn);
+		char_title_flex.fixed(&remove_btn, MOD_BTN_WIDTH);
+		char_title_flex.end();
+		pack.add(&char_title_flex);
 
 		// add the part with the name
 		let mut char_name_flex = Flex::default()
@@ -53,11 +98,21 @@ impl CharacterPack {
 		char_name_box.set_value(&character.name);
 		char_name_box.set_tab_nav(true);
 		char_name_flex.add(&char_name_box);
+		let status_lbl = Frame::default()
+			.with_label("Status:")
+			.with_align(Align::Right.union(Align::Inside));
+		char_name_flex.add(&status_lbl);
+		char_name_flex.fixed(&status_lbl, 50);
+		let mut status = new_status_counter(character.status);
+		status.set_tooltip("Compared against the NPC's Status when rolling");
+		char_name_flex.add(&status);
+		char_name_flex.fixed(&status, 50);
+		char_name_flex.end();
 
 		// handlers and references for auto-updating title
 		let char_title_ref = Rc::from(RefCell::from(char_title));
 		char_name_box.handle({
-			let char_title_ref = (&char_title_ref).clone();
+			let char_title_ref = char_title_ref.clone();
 			move |txt, ev| {
 				match ev {
 					Event::KeyDown => {
@@ -89,27 +144,90 @@ impl CharacterPack {
 			mod_lines.push(mod_line);
 		}//end creating gui widgets for each modifier
 
+		// add the row for adding a new modifier
+		let new_mod_line = NewModifierLine::new();
+		pack.add(&*new_mod_line);
+
 		CharacterPack {
 			pack,
 			// ux_title_ref: char_title_ref,
 			ux_char_name_box: char_name_box,
+			ux_status: status,
+			ux_up_btn: up_btn,
+			ux_down_btn: down_btn,
+			ux_duplicate_btn: duplicate_btn,
+			ux_remove_btn: remove_btn,
 			ux_mod_refs: mod_lines,
+			ux_new_mod_line: new_mod_line,
 		}//end struct construction
 	}//end new()
 }//end impl for CharacterPack
 
 widget_extends!(CharacterPack,Pack,pack);
 
+/// Creates a counter for editing a Status level.
+pub fn new_status_counter(status: i32) -> Counter {
+	let mut counter = Counter::default()
+		.with_type(CounterType::Simple);
+	counter.set_bounds(-10., 10.);
+	counter.set_step(1., 1);
+	counter.set_value(status.into());
+	counter.clear_visible_focus();
+	counter
+}//end new_status_counter()
+
+/// The width in pixels of each of the small buttons on a ModifierLine
+const MOD_BTN_WIDTH: i32 = 25;
+/// The width in pixels of the frame showing the value of a leveled trait
+const MOD_LEVEL_VALUE_WIDTH: i32 = 30;
+/// The width in pixels of the audience box on a ModifierLine
+const MOD_AUDIENCE_WIDTH: i32 = 80;
+/// The width in pixels of the reputation choice on a ModifierLine
+const MOD_REPUTATION_WIDTH: i32 = 65;
+/// The choice shown for a modifier that isn't a Reputation.
+/// After it, the choices follow the order of Recognition::ALL.
+const NOT_REPUTATION_CHOICE: &str = "Not Rep";
+/// The width in pixels of the situation choice on a ModifierLine
+const MOD_SITUATION_WIDTH: i32 = 75;
+/// The choice shown for a modifier that applies in every situation.
+/// After it, the choices follow the order of Situation::ALL.
+const ANY_SITUATION_CHOICE: &str = "Any Situation";
+/// The choice shown for a modifier that applies in more than one,
+/// but not every, situation. Only added when needed.
+const SEVERAL_SITUATIONS_CHOICE: &str = "Several";
+
 pub struct ModifierLine {
 	flex: Flex,
+	/// The modifier value, or the level if this is a leveled trait.
 	pub mod_value: Counter,
 	pub mod_check: CheckButton,
 	pub mod_text: Input,
+	/// The trait table, if the modifier is a leveled trait.
+	mod_leveled: Option<&'static TraitInfo>,
+	pub mod_audience: Input,
+	pub mod_reputation: Choice,
+	pub mod_situation: Choice,
+	/// The situations the modifier started with, kept for the several situations choice.
+	mod_situations: Vec<Situation>,
+	pub mod_up_btn: Button,
+	pub mod_down_btn: Button,
+	pub mod_remove_btn: Button,
 }//end struct ModifierLine
 
 impl ModifierLine {
-	/// Gets the modifier value for the represented ReactionMod.
-	pub fn get_mod_value(&self) -> i32 { self.mod_value.value().ceil() as i32 }//end get_mod_value()
+	/// Gets the modifier value for the represented ReactionMod.  
+	/// For a leveled trait, this comes from the level.
+	pub fn get_mod_value(&self) -> i32 {
+		match (self.mod_leveled, self.get_mod_level()) {
+			(Some(leveled), Some(level)) => leveled.modifier(level).unwrap_or(0),
+			_ => self.mod_value.value().ceil() as i32,
+		}//end matching whether this is a leveled trait
+	}//end get_mod_value()
+
+	/// Gets the level of the represented ReactionMod, if it's a leveled trait.
+	pub fn get_mod_level(&self) -> Option<i32> {
+		self.mod_leveled.as_ref().map(|_| self.mod_value.value().round() as i32)
+	}//end get_mod_level()
 
 	/// Gets the modifier enabled-ness for the represented ReactionMod.
 	pub fn get_mod_check(&self) -> bool { self.mod_check.is_checked() }//end get_mod_check()
@@ -117,12 +235,36 @@ impl ModifierLine {
 	/// Gets the modifier text for the represented ReactionMod.
 	pub fn get_mod_text(&self) -> String { self.mod_text.value() }//end get_mod_text()
 
+	/// Gets how often the represented ReactionMod is recognized,
+	/// or None if it isn't a Reputation.
+	pub fn get_mod_reputation(&self) -> Option<Recognition> {
+		match self.mod_reputation.value() {
+			index if index > 0 => Recognition::ALL.get(index as usize - 1).copied(),
+			_ => None,
+		}//end matching the choice to a recognition frequency
+	}//end get_mod_reputation()
+
+	/// Gets the situations the represented ReactionMod applies in.
+	pub fn get_mod_situations(&self) -> Vec<Situation> {
+		match self.mod_situation.value() {
+			0 => Vec::new(),
+			index => match Situation::ALL.get(index as usize - 1) {
+				Some(situation) => vec![*situation],
+				None => self.mod_situations.clone(),
+			},
+		}//end matching the choice to situations
+	}//end get_mod_situations()
+
 	/// Gets the full ReactionMod represented.
 	pub fn get_mod_full(&self) -> ReactionMod {
 		ReactionMod {
 			name: self.get_mod_text(),
 			modi: self.get_mod_value(),
 			enabled: self.get_mod_check(),
+			audience: audience::parse_conditions(&self.mod_audience.value()),
+			reputation: self.get_mod_reputation(),
+			situations: self.get_mod_situations(),
+			level: self.get_mod_level(),
 		}//end struct construction
 	}//end get_mod_full()
 
@@ -132,11 +274,21 @@ impl ModifierLine {
 			.with_size(0,25);
 		mod_box.set_margin(0);
 
+		let mod_leveled = modifier.leveled_trait();
 		let mut mod_value = Counter::default()
 		.with_type(CounterType::Simple);
-		mod_value.set_bounds(-99., 99.);
 		mod_value.set_step(1., 1);
-		mod_value.set_value(modifier.modi.into());
+		match (mod_leveled, modifier.level) {
+			(Some(leveled), Some(level)) => {
+				mod_value.set_bounds(leveled.min_level().into(), leveled.max_level().into());
+				mod_value.set_value(level.into());
+				mod_value.set_tooltip(&format!("Level of {}", leveled.name));
+			},
+			_ => {
+				mod_value.set_bounds(-99., 99.);
+				mod_value.set_value(modifier.modi.into());
+			},
+		}//end matching whether the counter is for a level or a value
 		mod_value.clear_visible_focus();
 		mod_box.add(&mod_value);
 		
@@ -150,6 +302,77 @@ impl ModifierLine {
 		let mut mod_text = Input::default();
 		mod_text.set_value(&modifier.name);
 
+		// leveled traits show the value from their table next to the name
+		if let Some(leveled) = mod_leveled {
+			mod_text.set_readonly(true);
+			let mut level_value = Frame::default()
+				.with_label(&format!("{:+}", modifier.modi));
+			level_value.set_frame(FrameType::GtkThinUpBox);
+			let level_lines: Vec<String> = leveled.levels.iter()
+				.map(|trait_level| format!("{}: {} ({:+})", trait_level.level, trait_level.name, trait_level.modifier))
+				.collect();
+			level_value.set_tooltip(&level_lines.join("\n"));
+			mod_box.fixed(&level_value, MOD_LEVEL_VALUE_WIDTH);
+		}//end if this is a leveled trait
+
+		let mut mod_audience = Input::default();
+		mod_audience.set_value(&audience::join_list(&modifier.audience));
+		mod_audience.set_tooltip("Who this applies to, like \"species=elf, !culture=Imperial\".\nLeave empty to apply to everyone.");
+		mod_box.fixed(&mod_audience, MOD_AUDIENCE_WIDTH);
+
+		let mut mod_reputation = Choice::default();
+		mod_reputation.add_choice(NOT_REPUTATION_CHOICE);
+		for recognition in Recognition::ALL {
+			mod_reputation.add_choice(&format!("Rep: {}", recognition));
+		}//end adding each recognition frequency as a choice
+		let reputation_index = match modifier.reputation {
+			Some(recognition) => Recognition::ALL.iter().position(|other| *other == recognition).map_or(0, |index| index + 1),
+			None => 0,
+		};
+		mod_reputation.set_value(reputation_index as i32);
+		mod_reputation.set_tooltip("Whether this is a Reputation, and how often the affected group recognizes it");
+		mod_reputation.clear_visible_focus();
+		mod_box.fixed(&mod_reputation, MOD_REPUTATION_WIDTH);
+
+		let mut mod_situation = Choice::default();
+		mod_situation.add_choice(ANY_SITUATION_CHOICE);
+		for situation in Situation::ALL {
+			mod_situation.add_choice(situation.name());
+		}//end adding each situation as a choice
+		let situation_index = match modifier.situations.as_slice() {
+			[] => 0,
+			[situation] => Situation::ALL.iter().position(|other| other == situation).map_or(0, |index| index + 1),
+			_ => {
+				mod_situation.add_choice(SEVERAL_SITUATIONS_CHOICE);
+				Situation::ALL.len() + 1
+			},
+		};
+		mod_situation.set_value(situation_index as i32);
+		let situation_names: Vec<&str> = modifier.situations.iter().map(|situation| situation.name()).collect();
+		if situation_names.len() > 1 {
+			mod_situation.set_tooltip(&format!("Which situation this applies in\nCurrently: {}", situation_names.join(", ")));
+		} else { mod_situation.set_tooltip("Which situation this applies in"); }
+		mod_situation.clear_visible_focus();
+		mod_box.fixed(&mod_situation, MOD_SITUATION_WIDTH);
+
+		let mut mod_up_btn = Button::default()
+			.with_label("@8->");
+		mod_up_btn.set_tooltip("Move modifier up");
+		mod_up_btn.clear_visible_focus();
+		mod_box.fixed(&mod_up_btn, MOD_BTN_WIDTH);
+
+		let mut mod_down_btn = Button::default()
+			.with_label("@2->");
+		mod_down_btn.set_tooltip("Move modifier down");
+		mod_down_btn.clear_visible_focus();
+		mod_box.fixed(&mod_down_btn, MOD_BTN_WIDTH);
+
+		let mut mod_remove_btn = Button::default()
+			.with_label("X");
+		mod_remove_btn.set_tooltip("Remove modifier");
+		mod_remove_btn.clear_visible_focus();
+		mod_box.fixed(&mod_remove_btn, MOD_BTN_WIDTH);
+
 		mod_box.end();
 
 		ModifierLine {
@@ -157,8 +380,111 @@ impl ModifierLine {
 			mod_value,
 			mod_check,
 			mod_text,
+			mod_leveled,
+			mod_audience,
+			mod_reputation,
+			mod_situation,
+			mod_situations: modifier.situations.clone(),
+			mod_up_btn,
+			mod_down_btn,
+			mod_remove_btn,
 		}
 	}//end new()
 }//end impl for ModifierLine
 
 widget_extends!(ModifierLine,Flex,flex);
+
+/// The tooltip for the name of a new modifier, when it isn't in the catalog.
+const NEW_MOD_TOOLTIP: &str = "Name of the new modifier. Start typing to search the trait catalog.\nFor a leveled trait like Charisma, the value is its level.";
+
+/// A row of widgets for typing in and adding a new ReactionMod.
+pub struct NewModifierLine {
+	flex: Flex,
+	/// The modifier value, or the level if the name is a leveled trait.
+	pub mod_value: Counter,
+	/// The name of the new modifier, which suggests traits from the catalog.
+	pub mod_text: InputChoice,
+	pub add_btn: Button,
+}//end struct NewModifierLine
+
+impl NewModifierLine {
+	/// Gets the modifier that has been typed in.  
+	/// If no name was given, a placeholder name is used.  
+	/// If the name is in the trait catalog, the modifier is made from that,
+	/// and the value is used as the level for leveled traits.
+	pub fn get_modifier(&self) -> ReactionMod {
+		let name = match self.mod_text.value() {
+			Some(name) if !name.trim().is_empty() => name,
+			_ => "New Modifier".to_string(),
+		};
+		let value = self.mod_value.value().ceil() as i32;
+		match traits::find_trait(&name) {
+			Some(info) => info.to_modifier(value),
+			None => ReactionMod::new(&name, value),
+		}//end matching whether the name is in the catalog
+	}//end get_modifier()
+
+	/// Creates a new, empty NewModifierLine.
+	pub fn new() -> NewModifierLine {
+		let mut new_mod_box = Flex::default()
+			.with_size(0,25);
+		new_mod_box.set_margin(0);
+
+		let mut mod_value = Counter::default()
+			.with_type(CounterType::Simple);
+		mod_value.set_bounds(-99., 99.);
+		mod_value.set_step(1., 1);
+		mod_value.set_value(0.);
+		mod_value.clear_visible_focus();
+		new_mod_box.fixed(&mod_value, 50);
+
+		let mut mod_text = InputChoice::default();
+		mod_text.set_tooltip(NEW_MOD_TOOLTIP);
+		mod_text.set_trigger(CallbackTrigger::Changed);
+		mod_text.set_callback({
+			let mut mod_value = mod_value.clone();
+			// the trait matched last time, so typing doesn't keep resetting the value
+			let mut last_match: Option<&'static str> = None;
+			move |mod_text| {
+				let name = mod_text.value().unwrap_or_default();
+				// suggest the traits that match what's been typed so far
+				mod_text.clear();
+				for info in traits::search(&name) {
+					mod_text.add(&escape_menu_label(&info.name));
+				}//end adding each matching trait as a suggestion
+				match traits::find_trait(&name) {
+					Some(info) => {
+						if last_match != Some(info.name.as_str()) {
+							if info.is_leveled() { mod_value.set_bounds(info.min_level().into(), info.max_level().into()); }
+							else { mod_value.set_bounds(-99., 99.); }
+							mod_value.set_value(info.default.into());
+							last_match = Some(info.name.as_str());
+						}//end if a different trait was picked
+						mod_text.set_tooltip(&info.to_string());
+					},
+					None => {
+						mod_value.set_bounds(-99., 99.);
+						mod_text.set_tooltip(NEW_MOD_TOOLTIP);
+						last_match = None;
+					},
+				}//end matching whether a trait from the catalog has been picked
+			}//end closure
+		});
+
+		let mut add_btn = Button::default()
+			.with_label("Add");
+		add_btn.set_tooltip("Add a new modifier");
+		new_mod_box.fixed(&add_btn, MOD_BTN_WIDTH * 3);
+
+		new_mod_box.end();
+
+		NewModifierLine {
+			flex: new_mod_box,
+			mod_value,
+			mod_text,
+			add_btn,
+		}//end struct construction
+	}//end new()
+}//end impl for NewModifierLine
+
+widget_extends!(NewModifierLine,Flex,flex);
@@ -0,0 +1,196 @@
+use fltk::{button::CheckButton, enums::{Align, FrameType}, frame::Frame, group::{Flex, FlexType, Pack}, input::Input, prelude::{GroupExt, InputExt, ValuatorExt, WidgetExt}, valuator::{Counter, CounterType}, widget_extends};
+use gurps_reactions::{audience, character::{Npc, ReactionMod}};
+
+use super::character_pack::{self, ModifierLine, NewModifierLine};
+
+/// A gui widget that displays and allows editing of the NPC
+/// being rolled for, along with any situational modifiers.
+pub struct NpcPack {
+	pack: Pack,
+	pub ux_npc_name_box: Input,
+	pub ux_base_attitude: Counter,
+	pub ux_status: Counter,
+	pub ux_resents_check: CheckButton,
+	pub ux_will: Counter,
+	pub ux_npc_tags_box: Input,
+	pub ux_npc_mod_refs: Vec<ModifierLine>,
+	pub ux_new_npc_mod_line: NewModifierLine,
+	pub ux_situation_mod_refs: Vec<ModifierLine>,
+	pub ux_new_situation_mod_line: NewModifierLine,
+}//end struct NpcPack
+
+impl NpcPack {
+	/// Gets the NPC represented by this NpcPack
+	pub fn get_npc(&self) -> Npc {
+		Npc {
+			name: self.ux_npc_name_box.value(),
+			base_attitude: self.ux_base_attitude.value().ceil() as i32,
+			reaction_modifiers: self.ux_npc_mod_refs.iter().map(|mod_ref| mod_ref.get_mod_full()).collect(),
+			tags: audience::parse_tags(&self.ux_npc_tags_box.value()),
+			status: self.ux_status.value().round() as i32,
+			resents_higher_status: self.ux_resents_check.is_checked(),
+			will: match self.ux_will.value().round() as i32 {
+				will if will > 0 => Some(will),
+				_ => None,
+			},
+		}//end struct construction
+	}//end get_npc()
+
+	/// Gets the situational modifiers represented by this NpcPack
+	pub fn get_situational_modifiers(&self) -> Vec<ReactionMod> {
+		self.ux_situation_mod_refs.iter().map(|mod_ref| mod_ref.get_mod_full()).collect()
+	}//end get_situational_modifiers()
+
+	/// Creates a new NpcPack out of the provided NPC and situational modifiers
+	pub fn new(npc: &Npc, situational: &[ReactionMod]) -> NpcPack {
+		let mut pack = Pack::default();
+		pack.set_spacing(2);
+
+		let mut npc_title = Frame::default()
+			.with_size(0, 30)
+			.with_label("NPC")
+			.with_align(Align::Inside.union(Align::Center));
+		npc_title.set_frame(FrameType::FlatBox);
+		pack.add(&npc_title);
+
+		// add the part with the name
+		let mut npc_name_flex = Flex::default()
+			.with_size(0,30)
+			.with_type(FlexType::Row);
+		pack.add(&npc_name_flex);
+		let npc_name_lbl = Frame::default()
+			.with_label("Name:")
+			.with_align(Align::Right.union(Align::Inside));
+		npc_name_flex.add(&npc_name_lbl);
+		npc_name_flex.fixed(&npc_name_lbl, 50);
+		let mut npc_name_box = Input::default();
+		npc_name_box.set_value(&npc.name);
+		npc_name_box.set_tab_nav(true);
+		npc_name_flex.add(&npc_name_box);
+		npc_name_flex.end();
+
+		// add the part with the base attitude
+		let mut attitude_flex = Flex::default()
+			.with_size(0,25)
+			.with_type(FlexType::Row);
+		pack.add(&attitude_flex);
+		let mut base_attitude = Counter::default()
+			.with_type(CounterType::Simple);
+		base_attitude.set_bounds(-99., 99.);
+		base_attitude.set_step(1., 1);
+		base_attitude.set_value(npc.base_attitude.into());
+		base_attitude.clear_visible_focus();
+		attitude_flex.add(&base_attitude);
+		attitude_flex.fixed(&base_attitude, 50);
+		let attitude_lbl = Frame::default()
+			.with_label("Base Attitude")
+			.with_align(Align::Left.union(Align::Inside));
+		attitude_flex.add(&attitude_lbl);
+		attitude_flex.end();
+
+		// add the part with the status
+		let mut status_flex = Flex::default()
+			.with_size(0,25)
+			.with_type(FlexType::Row);
+		pack.add(&status_flex);
+		let mut status = character_pack::new_status_counter(npc.status);
+		status.set_tooltip("Compared against each character's Status when rolling");
+		status_flex.add(&status);
+		status_flex.fixed(&status, 50);
+		let status_lbl = Frame::default()
+			.with_label("Status")
+			.with_align(Align::Left.union(Align::Inside));
+		status_flex.add(&status_lbl);
+		let mut resents_check = CheckButton::default()
+			.with_label("Resents Higher Status");
+		resents_check.set_checked(npc.resents_higher_status);
+		resents_check.set_tooltip("Characters with higher Status get a penalty instead of a bonus");
+		resents_check.clear_visible_focus();
+		status_flex.add(&resents_check);
+		status_flex.end();
+
+		// add the part with the will
+		let mut will_flex = Flex::default()
+			.with_size(0,25)
+			.with_type(FlexType::Row);
+		pack.add(&will_flex);
+		let mut will = Counter::default()
+			.with_type(CounterType::Simple);
+		will.set_bounds(0., 30.);
+		will.set_step(1., 1);
+		will.set_value(npc.will.unwrap_or(0).into());
+		will.set_tooltip("Influence skills are rolled against this in a Quick Contest.\nLeave at 0 to not roll against Will.");
+		will.clear_visible_focus();
+		will_flex.add(&will);
+		will_flex.fixed(&will, 50);
+		let will_lbl = Frame::default()
+			.with_label("Will")
+			.with_align(Align::Left.union(Align::Inside));
+		will_flex.add(&will_lbl);
+		will_flex.end();
+
+		// add the part with the tags that audience conditions check
+		let mut npc_tags_flex = Flex::default()
+			.with_size(0,30)
+			.with_type(FlexType::Row);
+		pack.add(&npc_tags_flex);
+		let npc_tags_lbl = Frame::default()
+			.with_label("Tags:")
+			.with_align(Align::Right.union(Align::Inside));
+		npc_tags_flex.add(&npc_tags_lbl);
+		npc_tags_flex.fixed(&npc_tags_lbl, 50);
+		let mut npc_tags_box = Input::default();
+		npc_tags_box.set_value(&audience::join_list(&npc.tags));
+		npc_tags_box.set_tooltip("What the NPC is, like \"species=elf, sex=female, culture=Sylvan\"");
+		npc_tags_box.set_tab_nav(true);
+		npc_tags_flex.add(&npc_tags_box);
+		npc_tags_flex.end();
+
+		// add the NPC's own modifiers
+		let npc_mod_lbl = Frame::default()
+			.with_size(0, 25)
+			.with_label("NPC Modifiers")
+			.with_align(Align::Left.union(Align::Inside));
+		pack.add(&npc_mod_lbl);
+		let mut npc_mod_lines = Vec::new();
+		for modifier in npc.reaction_modifiers.iter() {
+			let mod_line = ModifierLine::new(modifier);
+			pack.add(&*mod_line);
+			npc_mod_lines.push(mod_line);
+		}//end creating gui widgets for each NPC modifier
+		let new_npc_mod_line = NewModifierLine::new();
+		pack.add(&*new_npc_mod_line);
+
+		// add the modifiers from the situation
+		let situation_mod_lbl = Frame::default()
+			.with_size(0, 25)
+			.with_label("Situational Modifiers")
+			.with_align(Align::Left.union(Align::Inside));
+		pack.add(&situation_mod_lbl);
+		let mut situation_mod_lines = Vec::new();
+		for modifier in situational.iter() {
+			let mod_line = ModifierLine::new(modifier);
+			pack.add(&*mod_line);
+			situation_mod_lines.push(mod_line);
+		}//end creating gui widgets for each situational modifier
+		let new_situation_mod_line = NewModifierLine::new();
+		pack.add(&*new_situation_mod_line);
+		pack.end();
+
+		NpcPack {
+			pack,
+			ux_npc_name_box: npc_name_box,
+			ux_base_attitude: base_attitude,
+			ux_status: status,
+			ux_resents_check: resents_check,
+			ux_will: will,
+			ux_npc_tags_box: npc_tags_box,
+			ux_npc_mod_refs: npc_mod_lines,
+			ux_new_npc_mod_line: new_npc_mod_line,
+			ux_situation_mod_refs: situation_mod_lines,
+			ux_new_situation_mod_line: new_situation_mod_line,
+		}//end struct construction
+	}//end new()
+}//end impl for NpcPack
+
+widget_extends!(NpcPack,Pack,pack);
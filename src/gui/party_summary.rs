@@ -0,0 +1,145 @@
+use fltk::{button::CheckButton, enums::{Align, FrameType}, frame::Frame, group::{Flex, FlexType, Pack}, menu::Choice, prelude::{GroupExt, MenuExt, WidgetExt}, widget_extends};
+use gurps_reactions::character::{Character, Party, PartyMember, PartyRule};
+
+/// The width in pixels of the frames showing reaction sums in the summary
+const SUM_FRM_WIDTH: i32 = 40;
+/// The width in pixels of the choice for the party rule
+const RULE_CHOICE_WIDTH: i32 = 130;
+
+/// A gui widget that shows the reaction sum of each character,
+/// whether they're involved, and the total for the whole party.
+pub struct PartySummary {
+	pack: Pack,
+	pub ux_rule_choice: Choice,
+	pub ux_member_checks: Vec<CheckButton>,
+	ux_member_sums: Vec<Frame>,
+	ux_total_frm: Frame,
+}//end struct PartySummary
+
+impl PartySummary {
+	/// Gets whether each character is involved in the interaction.
+	pub fn get_involved(&self) -> Vec<bool> {
+		self.ux_member_checks.iter().map(|check| check.is_checked()).collect()
+	}//end get_involved()
+
+	/// Gets the rule currently selected for combining the party's modifiers.
+	pub fn get_rule(&self) -> PartyRule {
+		let index = self.ux_rule_choice.value().max(0) as usize;
+		PartyRule::ALL.get(index).copied().unwrap_or_default()
+	}//end get_rule()
+
+	/// Gets a party out of the characters, marking each
+	/// as involved or not based on the checks.
+	pub fn get_party(&self, characters: &[Character]) -> Party {
+		let involved = self.get_involved();
+		let members = characters.iter().enumerate()
+			.map(|(index, character)| PartyMember {
+				character: character.clone(),
+				involved: involved.get(index).copied().unwrap_or(true),
+			})
+			.collect();
+		Party {
+			members,
+			spokesperson: 0,
+		}//end struct construction
+	}//end get_party()
+
+	/// Refreshes the names, sums, and total shown for the characters.
+	pub fn update(&mut self, characters: &[Character]) {
+		for (index, character) in characters.iter().enumerate() {
+			if let Some(check) = self.ux_member_checks.get_mut(index) { check.set_label(&member_label(character)); }
+			if let Some(sum_frm) = self.ux_member_sums.get_mut(index) { sum_frm.set_label(&format!("{:+}", character.reaction_sum(false))); }
+		}//end updating each character's row
+		let total = self.get_party(characters).reaction(&self.get_rule(), false).total;
+		self.ux_total_frm.set_label(&format!("{:+}", total));
+		self.pack.redraw();
+	}//end update()
+
+	/// Creates a new party summary for the provided characters.
+	/// Characters without an entry in involved start out involved.
+	pub fn new(characters: &[Character], involved: &[bool], rule: PartyRule) -> PartySummary {
+		let mut pack = Pack::default();
+		pack.set_spacing(2);
+
+		// header with the rule for combining modifiers
+		let mut header_flex = Flex::default()
+			.with_size(0, 30)
+			.with_type(FlexType::Row);
+		header_flex.set_margin(0);
+		let mut header_lbl = Frame::default()
+			.with_label("Party")
+			.with_align(Align::Inside.union(Align::Left));
+		header_lbl.set_frame(FrameType::FlatBox);
+		header_flex.add(&header_lbl);
+		let mut rule_choice = Choice::default();
+		for party_rule in PartyRule::ALL {
+			rule_choice.add_choice(party_rule.name());
+		}//end adding each rule as a choice
+		let rule_index = PartyRule::ALL.iter().position(|party_rule| *party_rule == rule).unwrap_or(0);
+		rule_choice.set_value(rule_index as i32);
+		rule_choice.set_tooltip("How to combine the modifiers of involved characters");
+		header_flex.add(&rule_choice);
+		header_flex.fixed(&rule_choice, RULE_CHOICE_WIDTH);
+		header_flex.end();
+		pack.add(&header_flex);
+
+		// one row for each character
+		let mut member_checks = Vec::new();
+		let mut member_sums = Vec::new();
+		for (index, character) in characters.iter().enumerate() {
+			let mut member_flex = Flex::default()
+				.with_size(0, 25)
+				.with_type(FlexType::Row);
+			member_flex.set_margin(0);
+			let mut member_check = CheckButton::default()
+				.with_label(&member_label(character));
+			member_check.set_checked(involved.get(index).copied().unwrap_or(true));
+			member_check.set_tooltip("Involved in this interaction");
+			member_check.clear_visible_focus();
+			member_flex.add(&member_check);
+			let member_sum = Frame::default()
+				.with_label(&format!("{:+}", character.reaction_sum(false)));
+			member_flex.add(&member_sum);
+			member_flex.fixed(&member_sum, SUM_FRM_WIDTH);
+			member_flex.end();
+			pack.add(&member_flex);
+			member_checks.push(member_check);
+			member_sums.push(member_sum);
+		}//end adding a row for each character
+
+		// total for the whole party
+		let mut total_flex = Flex::default()
+			.with_size(0, 30)
+			.with_type(FlexType::Row);
+		total_flex.set_margin(0);
+		let total_lbl = Frame::default()
+			.with_label("Party Total:")
+			.with_align(Align::Inside.union(Align::Right));
+		total_flex.add(&total_lbl);
+		let mut total_frm = Frame::default();
+		total_frm.set_frame(FrameType::GtkThinUpBox);
+		total_flex.add(&total_frm);
+		total_flex.fixed(&total_frm, SUM_FRM_WIDTH);
+		total_flex.end();
+		pack.add(&total_flex);
+		pack.end();
+
+		let mut summary = PartySummary {
+			pack,
+			ux_rule_choice: rule_choice,
+			ux_member_checks: member_checks,
+			ux_member_sums: member_sums,
+			ux_total_frm: total_frm,
+		};
+		summary.update(characters);
+		summary
+	}//end new()
+}//end impl for PartySummary
+
+widget_extends!(PartySummary,Pack,pack);
+
+/// Gets the label to show for a character in the summary.
+fn member_label(character: &Character) -> String {
+	if character.name.is_empty() {"(unnamed)".to_string()}
+	else {character.name.clone()}
+}//end member_label()
@@ -0,0 +1,305 @@
+use std::{env, fmt, fs::{self, OpenOptions}, io::{self, Write}, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+use serde::{Deserialize, Serialize};
+
+use crate::{dice::Roll, influence::InfluenceRoll, reaction::{Encounter, ReactionBreakdown, ReactionLevel, ReactionResult, Situation}};
+
+/// One roll kept in the history, along with everything
+/// needed to see how it turned out later.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Character, Npc, Party, PartyRule, ReactionMod};
+/// # use gurps_reactions::dice::{Roll, RollSession};
+/// # use gurps_reactions::history::HistoryEntry;
+/// # use gurps_reactions::reaction::{Encounter, ReactionLevel};
+/// let mut bard = Character::new("Bard");
+/// bard.reaction_modifiers.push(ReactionMod::new("Voice", 2));
+/// let encounter = Encounter::new(Party::from_characters(vec![bard]), Npc::new("Innkeeper"));
+/// let dice = Roll::from_faces(&[5, 4, 3], 0).unwrap().dice;
+/// let result = encounter.roll_from_dice(&PartyRule::Spokesperson, &mut RollSession::seeded(1), dice.clone());
+///
+/// let entry = HistoryEntry::reaction(&encounter, &result, 0);
+/// assert_eq!(vec!["Bard".to_string()], entry.characters);
+/// assert_eq!("Innkeeper", entry.npc);
+/// assert_eq!(dice, entry.roll.dice);
+/// assert_eq!(2, entry.breakdown.total);
+/// assert_eq!(ReactionLevel::Good, entry.level);
+/// assert!(entry.involves("bard"));
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct HistoryEntry {
+	/// When the roll was made, in seconds since the Unix epoch.
+	pub timestamp: u64,
+	/// The names of the characters the NPC was reacting to.
+	pub characters: Vec<String>,
+	/// The name of the NPC doing the reacting.
+	pub npc: String,
+	/// The roll that decided the reaction, with the individual dice.
+	pub roll: Roll,
+	/// The modifiers that applied to a general reaction.
+	/// Empty when an influence skill decided the reaction.
+	pub breakdown: ReactionBreakdown,
+	/// The reaction level reached.
+	pub level: ReactionLevel,
+	/// The influence roll made in place of a reaction roll, if there was one,
+	/// including the target and the NPC's roll against Will.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub influence: Option<InfluenceRoll>,
+}//end struct HistoryEntry
+
+impl HistoryEntry {
+	/// Creates an entry for a reaction roll made for the encounter.
+	pub fn reaction(encounter: &Encounter, result: &ReactionResult, timestamp: u64) -> HistoryEntry {
+		HistoryEntry {
+			timestamp,
+			characters: involved_names(encounter),
+			npc: encounter.npc.name.clone(),
+			roll: result.roll.clone(),
+			breakdown: result.reaction(Situation::General)
+				.map(|reaction| reaction.breakdown.clone())
+				.unwrap_or_default(),
+			level: result.level(),
+			influence: None,
+		}//end struct construction
+	}//end reaction()
+
+	/// Creates an entry for an influence roll made for the encounter.
+	/// If the NPC got a normal reaction roll instead, fallback is recorded
+	/// in its place. Gives None if the influence roll didn't decide the
+	/// reaction and there's no fallback.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::{Npc, Party};
+	/// # use gurps_reactions::dice::{Roll, SuccessRoll};
+	/// # use gurps_reactions::history::HistoryEntry;
+	/// # use gurps_reactions::influence::{InfluenceRoll, InfluenceSkill};
+	/// # use gurps_reactions::reaction::{Encounter, ReactionLevel};
+	/// let encounter = Encounter::new(Party::new(), Npc::new("Guard"));
+	/// let skill = SuccessRoll::new(13, Roll::from_faces(&[4, 4, 4], 0).unwrap());
+	/// let will = SuccessRoll::new(12, Roll::from_faces(&[2, 2, 2], 0).unwrap());
+	/// let fast_talk = InfluenceRoll::new(InfluenceSkill::FastTalk, skill, Some(will.clone()));
+	///
+	/// let entry = HistoryEntry::influence(&encounter, &fast_talk, None, 0).unwrap();
+	/// assert_eq!(ReactionLevel::Bad, entry.level);
+	/// // the lost Quick Contest can be worked out again from the entry
+	/// assert_eq!(Some(will), entry.influence.unwrap().resistance);
+	/// ```
+	pub fn influence(encounter: &Encounter, influence: &InfluenceRoll, fallback: Option<&ReactionResult>, timestamp: u64) -> Option<HistoryEntry> {
+		let mut entry = match (influence.level(), fallback) {
+			(Some(level), _) => HistoryEntry {
+				timestamp,
+				characters: involved_names(encounter),
+				npc: encounter.npc.name.clone(),
+				roll: influence.roll.roll.clone(),
+				breakdown: ReactionBreakdown::default(),
+				level,
+				influence: None,
+			},
+			(None, Some(result)) => HistoryEntry::reaction(encounter, result, timestamp),
+			(None, None) => return None,
+		};
+		entry.influence = Some(influence.clone());
+		Some(entry)
+	}//end influence()
+
+	/// Whether the named character was part of this roll, ignoring case.
+	pub fn involves(&self, character: &str) -> bool {
+		let character = character.trim();
+		self.characters.iter().any(|name| name.eq_ignore_ascii_case(character))
+	}//end involves()
+}//end impl for HistoryEntry
+
+impl fmt::Display for HistoryEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let characters = if self.characters.is_empty() {"(nobody)".to_string()}
+			else {self.characters.join(", ")};
+		write!(f, "{} {} -> {}: {} ({})", timestamp_text(self.timestamp), characters, self.npc, self.roll, self.level.name())?;
+		if let Some(influence) = &self.influence { write!(f, " [{}]", influence.skill)?; }
+		Ok(())
+	}//end fmt()
+}//end impl Display for HistoryEntry
+
+/// A list of rolls, from oldest to newest.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::Roll;
+/// # use gurps_reactions::history::{HistoryEntry, RollHistory};
+/// # use gurps_reactions::reaction::{ReactionBreakdown, ReactionLevel};
+/// let entry = |timestamp, characters: &[&str]| HistoryEntry {
+///     timestamp,
+///     characters: characters.iter().map(|name| name.to_string()).collect(),
+///     npc: "Guard".to_string(),
+///     roll: Roll::from_faces(&[3, 3, 3], 0).unwrap(),
+///     breakdown: ReactionBreakdown::default(),
+///     level: ReactionLevel::Neutral,
+///     influence: None,
+/// };
+/// let mut history = RollHistory::new();
+/// history.push(entry(1, &["Bard"]));
+/// history.push(entry(2, &["Bard", "Thief"]));
+/// history.push(entry(3, &["Thief"]));
+///
+/// let bard: Vec<u64> = history.for_character("Bard").map(|entry| entry.timestamp).collect();
+/// assert_eq!(vec![1, 2], bard);
+///
+/// let json = history.to_json_lines().unwrap();
+/// assert_eq!(3, json.lines().count());
+/// assert_eq!(history, RollHistory::from_json_lines(&json).unwrap());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub struct RollHistory {
+	pub entries: Vec<HistoryEntry>,
+}//end struct RollHistory
+
+impl RollHistory {
+	/// Creates a new history with no rolls in it.
+	pub fn new() -> RollHistory {
+		RollHistory::default()
+	}//end new()
+
+	/// Adds a roll to the end of the history.
+	pub fn push(&mut self, entry: HistoryEntry) {
+		self.entries.push(entry);
+	}//end push()
+
+	/// Iterates over the rolls that the named character was part of, oldest first.
+	pub fn for_character<'a>(&'a self, character: &'a str) -> impl DoubleEndedIterator<Item = &'a HistoryEntry> {
+		self.entries.iter().filter(move |entry| entry.involves(character))
+	}//end for_character()
+
+	/// Converts the history into JSON lines, with one roll on each line.
+	pub fn to_json_lines(&self) -> Result<String, HistoryFileError> {
+		let mut json = String::new();
+		for entry in self.entries.iter() {
+			json.push_str(&serde_json::to_string(entry)?);
+			json.push('\n');
+		}//end writing each entry on its own line
+		Ok(json)
+	}//end to_json_lines()
+
+	/// Reads a history from JSON lines, with one roll on each line.
+	/// Blank lines are ignored.
+	pub fn from_json_lines(json: &str) -> Result<RollHistory, HistoryFileError> {
+		let entries = json.lines()
+			.filter(|line| !line.trim().is_empty())
+			.map(serde_json::from_str)
+			.collect::<Result<Vec<HistoryEntry>, serde_json::Error>>()?;
+		Ok(RollHistory { entries })
+	}//end from_json_lines()
+}//end impl for RollHistory
+
+/// The name of the history file within the data directory.
+const HISTORY_FILE_NAME: &str = "roll_history.jsonl";
+/// The name of the folder for this program within the per-user data directory.
+const DATA_DIR_NAME: &str = "gurps-reactions";
+
+/// Gets where the roll history is kept for the current user, which is
+/// in the per-user data directory for the platform.  
+/// Returns None if the data directory can't be found.
+pub fn default_history_path() -> Option<PathBuf> {
+	let env_dir = |name: &str| env::var_os(name).filter(|dir| !dir.is_empty()).map(PathBuf::from);
+	let data_dir = if cfg!(windows) {env_dir("APPDATA")}
+		else if cfg!(target_os = "macos") {env_dir("HOME").map(|home| home.join("Library").join("Application Support"))}
+		else {env_dir("XDG_DATA_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".local").join("share")))};
+	data_dir.map(|dir| dir.join(DATA_DIR_NAME).join(HISTORY_FILE_NAME))
+}//end default_history_path()
+
+/// Adds one roll to the end of a history file at the given path,
+/// creating the file and the folders it's in if they don't exist yet.
+pub fn append_history<P: AsRef<Path>>(path: P, entry: &HistoryEntry) -> Result<(), HistoryFileError> {
+	let mut line = serde_json::to_string(entry)?;
+	line.push('\n');
+	if let Some(parent) = path.as_ref().parent() { fs::create_dir_all(parent)?; }
+	let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+	file.write_all(line.as_bytes())?;
+	Ok(())
+}//end append_history()
+
+/// Reads the history file at the given path.
+/// A file that doesn't exist yet is read as an empty history.
+pub fn read_history<P: AsRef<Path>>(path: P) -> Result<RollHistory, HistoryFileError> {
+	match fs::read_to_string(path) {
+		Ok(json) => RollHistory::from_json_lines(&json),
+		Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(RollHistory::new()),
+		Err(err) => Err(err.into()),
+	}//end matching whether the file could be read
+}//end read_history()
+
+/// Gets the current time in seconds since the Unix epoch, for history entries.
+pub fn timestamp_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH)
+		.map_or(0, |duration| duration.as_secs())
+}//end timestamp_now()
+
+/// Formats a timestamp from history entries as a UTC date and time.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::history::timestamp_text;
+/// assert_eq!("1970-01-01 00:00", timestamp_text(0));
+/// assert_eq!("2024-02-29 13:45", timestamp_text(1_709_214_300));
+/// ```
+pub fn timestamp_text(timestamp: u64) -> String {
+	let days = (timestamp / 86_400) as i64;
+	let minutes = (timestamp % 86_400) / 60;
+	// convert days since the epoch into a civil date
+	let era_days = days + 719_468;
+	let era = era_days.div_euclid(146_097);
+	let day_of_era = era_days.rem_euclid(146_097);
+	let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 {month_index + 3} else {month_index - 9};
+	let year = year_of_era + era * 400 + if month <= 2 {1} else {0};
+	format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, minutes / 60, minutes % 60)
+}//end timestamp_text()
+
+/// Gets the names of the characters involved in an encounter.
+fn involved_names(encounter: &Encounter) -> Vec<String> {
+	encounter.party.members.iter()
+		.filter(|member| member.involved)
+		.map(|member| member.character.name.clone())
+		.collect()
+}//end involved_names()
+
+/// The ways that reading or writing a history file can fail.
+#[derive(Debug)]
+pub enum HistoryFileError {
+	/// The file couldn't be read or written.
+	Io(io::Error),
+	/// The file didn't hold valid roll history.
+	Json(serde_json::Error),
+}//end enum HistoryFileError
+
+impl fmt::Display for HistoryFileError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			HistoryFileError::Io(err) => write!(f, "couldn't access the history file: {}", err),
+			HistoryFileError::Json(err) => write!(f, "the history file isn't valid: {}", err),
+		}//end matching the kind of error
+	}//end fmt()
+}//end impl Display for HistoryFileError
+
+impl std::error::Error for HistoryFileError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			HistoryFileError::Io(err) => Some(err),
+			HistoryFileError::Json(err) => Some(err),
+		}//end matching the kind of error
+	}//end source()
+}//end impl Error for HistoryFileError
+
+impl From<io::Error> for HistoryFileError {
+	fn from(err: io::Error) -> Self { HistoryFileError::Io(err) }
+}//end impl From<io::Error> for HistoryFileError
+
+impl From<serde_json::Error> for HistoryFileError {
+	fn from(err: serde_json::Error) -> Self { HistoryFileError::Json(err) }
+}//end impl From<serde_json::Error> for HistoryFileError
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{dice::{ContestWinner, QuickContest, RollSession, SuccessRoll}, reaction::{ReactionLevel, SituationOutcome}};
+
+/// The skills that can be rolled in place of a reaction roll (B359).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum InfluenceSkill {
+	Diplomacy,
+	FastTalk,
+	Intimidation,
+	SavoirFaire,
+	SexAppeal,
+	Streetwise,
+}//end enum InfluenceSkill
+
+impl InfluenceSkill {
+	/// All the influence skills, in alphabetical order.
+	pub const ALL: [InfluenceSkill; 6] = [
+		InfluenceSkill::Diplomacy,
+		InfluenceSkill::FastTalk,
+		InfluenceSkill::Intimidation,
+		InfluenceSkill::SavoirFaire,
+		InfluenceSkill::SexAppeal,
+		InfluenceSkill::Streetwise,
+	];
+
+	/// The name of this skill, for display.
+	pub fn name(&self) -> &'static str {
+		match self {
+			InfluenceSkill::Diplomacy => "Diplomacy",
+			InfluenceSkill::FastTalk => "Fast-Talk",
+			InfluenceSkill::Intimidation => "Intimidation",
+			InfluenceSkill::SavoirFaire => "Savoir-Faire",
+			InfluenceSkill::SexAppeal => "Sex Appeal",
+			InfluenceSkill::Streetwise => "Streetwise",
+		}//end matching self to name
+	}//end name()
+
+	/// The reaction the NPC has when the skill roll succeeds.
+	pub fn success_level(&self) -> ReactionLevel { ReactionLevel::Good }
+
+	/// The reaction the NPC has when the skill roll fails.
+	/// None means the NPC gets a normal reaction roll instead.
+	pub fn failure_level(&self) -> Option<ReactionLevel> {
+		match self {
+			InfluenceSkill::Diplomacy => None,
+			InfluenceSkill::Intimidation => Some(ReactionLevel::VeryBad),
+			_ => Some(ReactionLevel::Bad),
+		}//end matching self to the level on a failure
+	}//end failure_level()
+
+	/// A short note on when the skill works and what it does.
+	pub fn notes(&self) -> &'static str {
+		match self {
+			InfluenceSkill::Diplomacy => "Safe to try: on a failure, the NPC gets a normal reaction roll instead.",
+			InfluenceSkill::FastTalk => "Works quickly, but the NPC may realize they've been tricked later.",
+			InfluenceSkill::Intimidation => "On a success the NPC goes along out of fear. On a failure they react very badly.",
+			InfluenceSkill::SavoirFaire => "Only works in high society, on those who care about good manners.",
+			InfluenceSkill::SexAppeal => "Only works on those who could be attracted to you.",
+			InfluenceSkill::Streetwise => "Only works on criminals and others in the underworld.",
+		}//end matching self to notes
+	}//end notes()
+}//end impl for InfluenceSkill
+
+impl std::fmt::Display for InfluenceSkill {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}//end fmt()
+}//end impl Display for InfluenceSkill
+
+/// A roll against an influence skill, made in place of a reaction roll.  
+/// If the NPC's Will is known, this is a Quick Contest against it.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::dice::{Die, Roll, SuccessRoll};
+/// # use gurps_reactions::influence::{InfluenceRoll, InfluenceSkill};
+/// # use gurps_reactions::reaction::ReactionLevel;
+/// let roll = |a, b, c| Roll::from_dice(vec![Die {result: a}, Die {result: b}, Die {result: c}], 0);
+/// let fast_talk = InfluenceRoll::new(InfluenceSkill::FastTalk, SuccessRoll::new(13, roll(4, 4, 4)), None);
+/// assert!(fast_talk.succeeded());
+/// assert_eq!(Some(ReactionLevel::Good), fast_talk.level());
+///
+/// // against Will, it isn't enough to just succeed
+/// let will = SuccessRoll::new(12, roll(2, 2, 2));
+/// let fast_talk = InfluenceRoll::new(InfluenceSkill::FastTalk, SuccessRoll::new(13, roll(4, 4, 4)), Some(will));
+/// assert!(!fast_talk.succeeded());
+/// assert_eq!(Some(ReactionLevel::Bad), fast_talk.level());
+///
+/// let diplomacy = InfluenceRoll::new(InfluenceSkill::Diplomacy, SuccessRoll::new(10, roll(4, 4, 4)), None);
+/// assert!(!diplomacy.succeeded());
+/// // a failed Diplomacy roll means rolling a normal reaction instead
+/// assert_eq!(None, diplomacy.level());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct InfluenceRoll {
+	/// The skill that was rolled.
+	pub skill: InfluenceSkill,
+	/// The roll against the effective skill level, including any modifiers.
+	pub roll: SuccessRoll,
+	/// The NPC's roll against Will, if they got one.
+	pub resistance: Option<SuccessRoll>,
+}//end struct InfluenceRoll
+
+impl InfluenceRoll {
+	/// Creates an influence roll out of rolls that have already been made.
+	pub fn new(skill: InfluenceSkill, roll: SuccessRoll, resistance: Option<SuccessRoll>) -> InfluenceRoll {
+		InfluenceRoll {
+			skill,
+			roll,
+			resistance,
+		}//end struct construction
+	}//end new()
+
+	/// Rolls against the skill using the session, and against the NPC's Will if it's given.
+	pub fn roll(skill: InfluenceSkill, skill_level: i32, will: Option<i32>, session: &mut RollSession) -> InfluenceRoll {
+		let roll = session.success_roll(skill_level);
+		let resistance = will.map(|will| session.success_roll(will));
+		InfluenceRoll::new(skill, roll, resistance)
+	}//end roll()
+
+	/// Gets the Quick Contest against Will, if the NPC resisted.
+	pub fn contest(&self) -> Option<QuickContest> {
+		self.resistance.as_ref().map(|resistance| QuickContest::new(self.roll.clone(), resistance.clone()))
+	}//end contest()
+
+	/// Whether the influence worked. Against Will, the skill
+	/// has to win the Quick Contest, and otherwise just succeed.
+	pub fn succeeded(&self) -> bool {
+		match self.contest() {
+			Some(contest) => contest.winner() == ContestWinner::First,
+			None => self.roll.succeeded(),
+		}//end matching whether there was a contest
+	}//end succeeded()
+
+	/// The reaction the NPC has because of this roll, or None if
+	/// the NPC should get a normal reaction roll instead.
+	pub fn level(&self) -> Option<ReactionLevel> {
+		if self.succeeded() {Some(self.skill.success_level())}
+		else {self.skill.failure_level()}
+	}//end level()
+
+	/// Gets the outcome of this roll in every situation, or None if
+	/// the NPC should get a normal reaction roll instead.
+	pub fn outcomes(&self) -> Option<Vec<SituationOutcome>> {
+		self.level().map(|level| level.outcomes())
+	}//end outcomes()
+}//end impl for InfluenceRoll
+
+impl std::fmt::Display for InfluenceRoll {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {}", self.skill, self.roll)?;
+		if let Some(contest) = self.contest() {
+			let result = match contest.winner() {
+				ContestWinner::First => "won",
+				ContestWinner::Second => "lost",
+				ContestWinner::Tie => "tied",
+			};
+			write!(f, "\nWill: {}\n{} the Quick Contest by {}", contest.second, result, contest.margin_of_victory())?;
+		}//end if there was a contest against Will
+		Ok(())
+	}//end fmt()
+}//end impl Display for InfluenceRoll
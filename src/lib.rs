@@ -6,4 +6,28 @@ pub mod character;
 /// This module holds data and functions for rolling
 /// dice. The intent is to have functions that allow
 /// you to store both the total and individual stuff.
-pub mod dice;
\ No newline at end of file
+pub mod dice;
+
+/// This module holds the rules for turning a reaction
+/// roll into how an NPC actually reacts.
+pub mod reaction;
+
+/// This module holds data and functions for limiting
+/// reaction modifiers to certain kinds of NPCs.
+pub mod audience;
+
+/// This module holds the built-in catalog of traits that
+/// affect reactions, including tables for leveled traits.
+/// The catalog is deliberately partial: it only holds traits
+/// that give a set reaction modifier. Traits whose effect is
+/// up to the GM, like Xenophilia, Sense of Duty or Empathy,
+/// should be added as custom modifiers instead.
+pub mod traits;
+
+/// This module holds the rules for rolling influence
+/// skills in place of a reaction roll.
+pub mod influence;
+
+/// This module holds data and functions for keeping
+/// a history of rolls and saving it to a file.
+pub mod history;
@@ -1,5 +1,5 @@
-use gui::{InterfaceMessage, GUI};
-use gurps_reactions::character::{Character, ReactionMod};
+use gui::{InterfaceMessage, ModifierOwner, GUI};
+use gurps_reactions::{character::{self, Character, Party, ReactionMod}, dice::{Roll, RollSession, SuccessRoll}, history::{self, HistoryEntry}, influence::InfluenceRoll, reaction::Encounter};
 
 mod gui;
 
@@ -7,23 +7,206 @@ fn main() {
     eprintln!("Hello, world!");
     let mut gui = GUI::initialize();
     let recv = gui.get_receiver();
+    let mut roll_session = RollSession::new();
+    let history_path = history::default_history_path();
+    if history_path.is_none() { GUI::show_error("Couldn't find a folder to keep the roll history in, so it won't be saved."); }
+    let mut roll_history = match &history_path {
+        Some(path) => history::read_history(path).unwrap_or_else(|err| {
+            GUI::show_error(&format!("Couldn't read the roll history in {}:\n{}", path.display(), err));
+            history::RollHistory::new()
+        }),
+        None => history::RollHistory::new(),
+    };
+    gui.set_history_display(&roll_history, None);
 
     while gui.wait() {
         match recv.recv() {
             Some(InterfaceMessage::NewCharacter) => {
-                let mut bob = Character::new("bob");
-                bob.reaction_modifiers.push(ReactionMod::new("Ugly",-6));
-                bob.reaction_modifiers.push(ReactionMod::new("Kind", 2));
-                gui.set_character_display(&vec![bob]);
+                let mut characters = gui.get_characters();
+                characters.push(Character::new(""));
+                gui.set_character_display(&characters);
+            },
+            Some(InterfaceMessage::RemoveCharacter(char_index)) => {
+                edit_characters(&mut gui, |characters, involved, targeted| {
+                    if char_index < characters.len() { characters.remove(char_index); }
+                    if char_index < involved.len() { involved.remove(char_index); }
+                    if char_index < targeted.len() { targeted.remove(char_index); }
+                });
+            },
+            Some(InterfaceMessage::DuplicateCharacter(char_index)) => {
+                edit_characters(&mut gui, |characters, involved, targeted| {
+                    if let Some(character) = characters.get(char_index) {
+                        let mut copy = character.clone();
+                        copy.name = format!("{} (copy)", copy.name);
+                        characters.insert(char_index + 1, copy);
+                        let copy_involved = involved.get(char_index).copied().unwrap_or(true);
+                        involved.insert((char_index + 1).min(involved.len()), copy_involved);
+                        targeted.insert(char_index + 1, false);
+                    }//end if we found the character to copy
+                });
+            },
+            Some(InterfaceMessage::MoveCharacterUp(char_index)) => {
+                edit_characters(&mut gui, |characters, involved, targeted| {
+                    move_item(characters, char_index, true);
+                    move_item(involved, char_index, true);
+                    move_item(targeted, char_index, true);
+                });
+            },
+            Some(InterfaceMessage::MoveCharacterDown(char_index)) => {
+                edit_characters(&mut gui, |characters, involved, targeted| {
+                    move_item(characters, char_index, false);
+                    move_item(involved, char_index, false);
+                    move_item(targeted, char_index, false);
+                });
+            },
+            Some(InterfaceMessage::SaveCharacterAs) => {
+                if let Some(path) = gui.choose_save_path() {
+                    let characters = gui.get_characters();
+                    if let Err(err) = character::write_characters(&path, &characters) {
+                        GUI::show_error(&format!("Couldn't save to {}:\n{}", path.display(), err));
+                    }//end if saving failed
+                }//end if the user chose somewhere to save
+            },
+            Some(InterfaceMessage::OpenCharacter) => {
+                if let Some(path) = gui.choose_open_path() {
+                    match character::read_characters(&path) {
+                        Ok(characters) => {
+                            gui.set_character_display(&characters);
+                            // the opened characters have nothing to do with who was involved before
+                            gui.set_involved(&vec![true; characters.len()]);
+                            gui.set_roll_target(None);
+                        },
+                        Err(err) => GUI::show_error(&format!("Couldn't open {}:\n{}", path.display(), err)),
+                    }//end matching whether the file could be read
+                }//end if the user chose a file to open
             },
-            Some(InterfaceMessage::SaveCharacterAs) => println!("Save Character As"),
-            Some(InterfaceMessage::OpenCharacter) => println!("Open Character"),
             Some(InterfaceMessage::EditCharacter) => {
                 let characters = gui.get_characters();
                 gui.set_character_display(&characters);
             }
+            Some(InterfaceMessage::EditParty) => gui.update_party_summary(),
+            Some(InterfaceMessage::Roll) => {
+                let target = gui.get_roll_target().and_then(|index| gui.get_characters().into_iter().nth(index));
+                let party = match &target {
+                    Some(character) => Party::from_characters(vec![character.clone()]),
+                    None => gui.get_party(),
+                };
+                let mut encounter = Encounter::new(party, gui.get_npc());
+                encounter.situational = gui.get_situational_modifiers();
+                let (times, keep) = gui.get_roll_count();
+                let manual_dice = match gui.get_manual_dice() {
+                    Some(Ok(dice)) => Some(dice),
+                    Some(Err(err)) => {
+                        GUI::show_error(&format!("Couldn't use the physical dice: {}", err));
+                        continue;
+                    },
+                    None => None,
+                };
+                let entry = match gui.get_influence() {
+                    Some((skill, skill_level)) => {
+                        let influence = match manual_dice {
+                            Some(dice) => {
+                                let roll = SuccessRoll::new(skill_level, Roll::from_dice(dice, 0));
+                                let resistance = encounter.npc.will.map(|will| roll_session.success_roll(will));
+                                InfluenceRoll::new(skill, roll, resistance)
+                            },
+                            None => InfluenceRoll::roll(skill, skill_level, encounter.npc.will, &mut roll_session),
+                        };
+                        let fallback = match influence.level() {
+                            Some(_) => None,
+                            None => Some(encounter.roll_keeping(&gui.get_party_rule(), &mut roll_session, times, keep)),
+                        };
+                        gui.set_influence_display(&influence, fallback.as_ref());
+                        HistoryEntry::influence(&encounter, &influence, fallback.as_ref(), history::timestamp_now())
+                    },
+                    None => {
+                        let result = match manual_dice {
+                            Some(dice) => encounter.roll_from_dice(&gui.get_party_rule(), &mut roll_session, dice),
+                            None => encounter.roll_keeping(&gui.get_party_rule(), &mut roll_session, times, keep),
+                        };
+                        gui.set_roll_display(&result);
+                        Some(HistoryEntry::reaction(&encounter, &result, history::timestamp_now()))
+                    },
+                };
+                if let Some(entry) = entry {
+                    if let Some(path) = &history_path {
+                        if let Err(err) = history::append_history(path, &entry) {
+                            GUI::show_error(&format!("Couldn't add the roll to {}:\n{}", path.display(), err));
+                        }//end if saving the roll failed
+                    }//end if there's somewhere to save the roll
+                    roll_history.push(entry);
+                }//end if there was a roll to remember
+                gui.set_history_display(&roll_history, target.as_ref().map(|character| character.name.as_str()));
+            },
+            Some(InterfaceMessage::AddModifier(owner)) => {
+                if let Some(new_mod) = gui.get_new_modifier(owner) {
+                    edit_modifiers(&mut gui, owner, |modifiers| modifiers.push(new_mod));
+                }//end if we found the new modifier
+            },
+            Some(InterfaceMessage::RemoveModifier(owner, mod_index)) => {
+                edit_modifiers(&mut gui, owner, |modifiers| {
+                    if mod_index < modifiers.len() { modifiers.remove(mod_index); }
+                });
+            },
+            Some(InterfaceMessage::MoveModifierUp(owner, mod_index)) => {
+                edit_modifiers(&mut gui, owner, |modifiers| move_item(modifiers, mod_index, true));
+            },
+            Some(InterfaceMessage::MoveModifierDown(owner, mod_index)) => {
+                edit_modifiers(&mut gui, owner, |modifiers| move_item(modifiers, mod_index, false));
+            },
             None => {},
         }//end matching messages received
     }//end looping while gui is up
     eprintln!("World ending!");
 }//end main function
+
+/// Applies an edit to the list of characters, along with whether each is
+/// involved and which is the roll target, then updates the display to match.
+/// The edit should move things around in all three lists the same way.
+fn edit_characters<F: FnOnce(&mut Vec<Character>, &mut Vec<bool>, &mut Vec<bool>)>(gui: &mut GUI, edit: F) {
+    let mut characters = gui.get_characters();
+    let mut involved = gui.get_involved();
+    let mut targeted = roll_target_marks(gui.get_roll_target(), characters.len());
+    edit(&mut characters, &mut involved, &mut targeted);
+    gui.set_character_display(&characters);
+    gui.set_involved(&involved);
+    gui.set_roll_target(targeted.iter().position(|is_target| *is_target));
+}//end edit_characters()
+
+/// Marks which character is the roll target, so the mark can
+/// be moved around along with the characters.
+fn roll_target_marks(target: Option<usize>, count: usize) -> Vec<bool> {
+    (0..count).map(|index| Some(index) == target).collect()
+}//end roll_target_marks()
+
+/// Swaps the item at index with the one before it (if up) or after it.
+/// Does nothing if there's nowhere to move to.
+fn move_item<T>(items: &mut [T], index: usize, up: bool) {
+    let other = if up {index.checked_sub(1)} else {index.checked_add(1)};
+    match other {
+        Some(other) if index < items.len() && other < items.len() => items.swap(index, other),
+        _ => {},
+    }//end matching whether we can move the item
+}//end move_item()
+
+/// Applies an edit to the list of modifiers belonging to the owner,
+/// then updates the display to match.
+fn edit_modifiers<F: FnOnce(&mut Vec<ReactionMod>)>(gui: &mut GUI, owner: ModifierOwner, edit: F) {
+    match owner {
+        ModifierOwner::Character(char_index) => {
+            let mut characters = gui.get_characters();
+            if let Some(character) = characters.get_mut(char_index) { edit(&mut character.reaction_modifiers); }
+            gui.set_character_display(&characters);
+        },
+        ModifierOwner::Npc => {
+            let mut npc = gui.get_npc();
+            edit(&mut npc.reaction_modifiers);
+            gui.set_npc_display(&npc, &gui.get_situational_modifiers());
+        },
+        ModifierOwner::Situation => {
+            let mut situational = gui.get_situational_modifiers();
+            edit(&mut situational);
+            gui.set_npc_display(&gui.get_npc(), &situational);
+        },
+    }//end matching who the modifiers belong to
+}//end edit_modifiers()
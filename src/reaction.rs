@@ -0,0 +1,903 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{character::{Character, Npc, Party, PartyStrategy, ReactionMod, Recognition}, dice::{Die, Distribution, KeepRule, KeptRoll, Roll, RollSession, SuccessRoll}};
+
+/// The different levels of reaction from the Reaction Table (B560).  
+/// These are ordered from worst to best, so comparisons like
+/// `level >= ReactionLevel::Neutral` work as expected.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum ReactionLevel {
+	/// 0 or less
+	Disastrous,
+	/// 1 to 3
+	VeryBad,
+	/// 4 to 6
+	Bad,
+	/// 7 to 9
+	Poor,
+	/// 10 to 12
+	Neutral,
+	/// 13 to 15
+	Good,
+	/// 16 to 18
+	VeryGood,
+	/// 19 or more
+	Excellent,
+}//end enum ReactionLevel
+
+impl ReactionLevel {
+	/// All the reaction levels, from worst to best.
+	pub const ALL: [ReactionLevel; 8] = [
+		ReactionLevel::Disastrous,
+		ReactionLevel::VeryBad,
+		ReactionLevel::Bad,
+		ReactionLevel::Poor,
+		ReactionLevel::Neutral,
+		ReactionLevel::Good,
+		ReactionLevel::VeryGood,
+		ReactionLevel::Excellent,
+	];
+
+	/// Looks up the reaction level for a modified 3d6 total,
+	/// using the thresholds from the Reaction Table.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::reaction::ReactionLevel;
+	/// assert_eq!(ReactionLevel::Disastrous, ReactionLevel::from_total(-4));
+	/// assert_eq!(ReactionLevel::VeryBad, ReactionLevel::from_total(1));
+	/// assert_eq!(ReactionLevel::Poor, ReactionLevel::from_total(9));
+	/// assert_eq!(ReactionLevel::Neutral, ReactionLevel::from_total(10));
+	/// assert_eq!(ReactionLevel::Good, ReactionLevel::from_total(15));
+	/// assert_eq!(ReactionLevel::Excellent, ReactionLevel::from_total(25));
+	/// ```
+	pub fn from_total(total: i32) -> ReactionLevel {
+		match total {
+			i32::MIN..=0 => ReactionLevel::Disastrous,
+			1..=3 => ReactionLevel::VeryBad,
+			4..=6 => ReactionLevel::Bad,
+			7..=9 => ReactionLevel::Poor,
+			10..=12 => ReactionLevel::Neutral,
+			13..=15 => ReactionLevel::Good,
+			16..=18 => ReactionLevel::VeryGood,
+			19..=i32::MAX => ReactionLevel::Excellent,
+		}//end matching total to the reaction table
+	}//end from_total()
+
+	/// The range of modified totals that give this reaction level.  
+	/// The open ends of the table are given as i32::MIN and i32::MAX.
+	pub fn total_range(&self) -> std::ops::RangeInclusive<i32> {
+		match self {
+			ReactionLevel::Disastrous => i32::MIN..=0,
+			ReactionLevel::VeryBad => 1..=3,
+			ReactionLevel::Bad => 4..=6,
+			ReactionLevel::Poor => 7..=9,
+			ReactionLevel::Neutral => 10..=12,
+			ReactionLevel::Good => 13..=15,
+			ReactionLevel::VeryGood => 16..=18,
+			ReactionLevel::Excellent => 19..=i32::MAX,
+		}//end matching self to range of totals
+	}//end total_range()
+
+	/// The name of this reaction level, as written on the Reaction Table.
+	pub fn name(&self) -> &'static str {
+		match self {
+			ReactionLevel::Disastrous => "Disastrous",
+			ReactionLevel::VeryBad => "Very Bad",
+			ReactionLevel::Bad => "Bad",
+			ReactionLevel::Poor => "Poor",
+			ReactionLevel::Neutral => "Neutral",
+			ReactionLevel::Good => "Good",
+			ReactionLevel::VeryGood => "Very Good",
+			ReactionLevel::Excellent => "Excellent",
+		}//end matching self to name
+	}//end name()
+}//end impl for ReactionLevel
+
+impl std::fmt::Display for ReactionLevel {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}//end fmt()
+}//end impl Display for ReactionLevel
+
+/// The different situations on the Reaction Table (B560-562),
+/// each of which has its own idea of what a reaction means.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum Situation {
+	/// A general reaction, when nothing more specific applies.
+	General,
+	/// An encounter that could turn into a fight.
+	PotentialCombat,
+	/// Buying and selling.
+	Commercial,
+	/// Asking the NPC for help.
+	RequestForAid,
+	/// Asking the NPC for information.
+	RequestForInfo,
+	/// How loyal an employee or follower is.
+	Loyalty,
+}//end enum Situation
+
+impl Situation {
+	/// All the situations, in the order they're listed in the results.
+	pub const ALL: [Situation; 6] = [
+		Situation::General,
+		Situation::PotentialCombat,
+		Situation::Commercial,
+		Situation::RequestForAid,
+		Situation::RequestForInfo,
+		Situation::Loyalty,
+	];
+
+	/// The name of this situation, for display.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Situation::General => "General Reaction",
+			Situation::PotentialCombat => "Potential Combat",
+			Situation::Commercial => "Commercial Transactions",
+			Situation::RequestForAid => "Requests for Aid",
+			Situation::RequestForInfo => "Requests for Info",
+			Situation::Loyalty => "Loyalty",
+		}//end matching self to name
+	}//end name()
+}//end impl for Situation
+
+impl std::fmt::Display for Situation {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}//end fmt()
+}//end impl Display for Situation
+
+/// What an NPC does in one specific situation, given their reaction level.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct SituationOutcome {
+	/// The situation this outcome is for.
+	pub situation: Situation,
+	/// The reaction level that produced this outcome.
+	pub level: ReactionLevel,
+	/// A short description of how the NPC behaves.
+	pub description: String,
+}//end struct SituationOutcome
+
+impl SituationOutcome {
+	/// Looks up the outcome of a reaction level in a given situation.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::reaction::{ReactionLevel, Situation, SituationOutcome};
+	/// let outcome = SituationOutcome::new(Situation::Commercial, ReactionLevel::Bad);
+	/// assert_eq!(Situation::Commercial, outcome.situation);
+	/// assert!(outcome.to_string().starts_with("Commercial Transactions: "));
+	/// ```
+	pub fn new(situation: Situation, level: ReactionLevel) -> SituationOutcome {
+		SituationOutcome {
+			situation,
+			level,
+			description: outcome_text(situation, level).to_string(),
+		}//end struct construction
+	}//end new()
+}//end impl for SituationOutcome
+
+impl std::fmt::Display for SituationOutcome {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {}", self.situation, self.description)
+	}//end fmt()
+}//end impl Display for SituationOutcome
+
+impl ReactionLevel {
+	/// Gets the outcome of this reaction level in a single situation.
+	pub fn outcome(&self, situation: Situation) -> SituationOutcome {
+		SituationOutcome::new(situation, *self)
+	}//end outcome()
+
+	/// Gets the outcome of this reaction level in every situation,
+	/// in the same order as Situation::ALL.
+	pub fn outcomes(&self) -> Vec<SituationOutcome> {
+		Situation::ALL.iter().map(|situation| self.outcome(*situation)).collect()
+	}//end outcomes()
+}//end impl for ReactionLevel
+
+/// The text of the Reaction Table, summarized for each situation and level.
+fn outcome_text(situation: Situation, level: ReactionLevel) -> &'static str {
+	use ReactionLevel::*;
+	match situation {
+		Situation::General => match level {
+			Disastrous => "Hates you and acts against your interests, whatever it costs.",
+			VeryBad => "Dislikes you and works against you if it's convenient.",
+			Bad => "Doesn't care about you and acts against you if there's profit in it.",
+			Poor => "Unimpressed; may make threats or want a bribe before helping.",
+			Neutral => "Ignores you as much as possible.",
+			Good => "Likes you and will be helpful within normal limits.",
+			VeryGood => "Thinks highly of you and is quite friendly and helpful.",
+			Excellent => "Extremely impressed; acts in your best interests as far as it can.",
+		},
+		Situation::PotentialCombat => match level {
+			Disastrous => "Attacks viciously, asking no quarter and giving none.",
+			VeryBad => "Attacks, fleeing only if it's clearly outmatched.",
+			Bad => "Attacks unless outnumbered; if outnumbered, backs off to try again later.",
+			Poor => "Shouts threats or insults and demands you leave; attacks if you look weak.",
+			Neutral => "Goes its own way and lets you go yours.",
+			Good => "Friendly; even a sworn enemy finds a reason to let you go.",
+			VeryGood => "Friendly, and may offer help if you're in trouble.",
+			Excellent => "Very friendly; may even join you for a while.",
+		},
+		Situation::Commercial => match level {
+			Disastrous => "Won't deal with you at all, and may try to cheat or rob you.",
+			VeryBad => "Asks three times the fair price, or offers a third of it.",
+			Bad => "Asks twice the fair price, or offers half of it.",
+			Poor => "Asks 20% over the fair price, or offers 20% under it.",
+			Neutral => "Buys and sells at the fair price.",
+			Good => "Gives you a 10% better deal than the fair price.",
+			VeryGood => "Gives you a 20% better deal than the fair price.",
+			Excellent => "Gives you the best deal possible, and may throw in extra help.",
+		},
+		Situation::RequestForAid => match level {
+			Disastrous => "Refuses, and does what it can to make things worse for you.",
+			VeryBad => "Refuses, and may get in your way.",
+			Bad => "Refuses.",
+			Poor => "Refuses, unless bribed or pressured.",
+			Neutral => "Helps only if it's easy and safe.",
+			Good => "Helps, as long as it isn't dangerous or costly.",
+			VeryGood => "Helps, even at some risk or cost.",
+			Excellent => "Goes out of its way to help, at real risk or cost.",
+		},
+		Situation::RequestForInfo => match level {
+			Disastrous => "Lies maliciously to lead you into harm.",
+			VeryBad => "Lies or deliberately misleads you.",
+			Bad => "Tells you nothing, or lies if there's something in it.",
+			Poor => "Claims not to know, but might talk for a bribe.",
+			Neutral => "Answers simple questions, but won't go into detail.",
+			Good => "Answers truthfully with what it knows.",
+			VeryGood => "Answers fully and volunteers useful related information.",
+			Excellent => "Tells you everything it knows, and offers to find out more.",
+		},
+		Situation::Loyalty => match level {
+			Disastrous => "Hates you and betrays you at the first chance.",
+			VeryBad => "Dislikes you and deserts or betrays you given any chance.",
+			Bad => "Unreliable; betrays you for a good enough offer.",
+			Poor => "Only in it for the pay; leaves for a better offer.",
+			Neutral => "Does the job, but won't risk much for you.",
+			Good => "Loyal and hard-working.",
+			VeryGood => "Very loyal; takes risks on your behalf.",
+			Excellent => "Fanatically loyal; would die for you.",
+		},
+	}//end matching situation and level to text
+}//end outcome_text()
+
+/// The largest modifier, positive or negative, that makes a difference to ReactionOdds.
+const ODDS_MODIFIER_LIMIT: i32 = 20;
+
+/// The exact chance of getting each reaction level on a 3d6 reaction roll
+/// with a particular modifier.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Character, ReactionMod};
+/// # use gurps_reactions::reaction::{ReactionLevel, ReactionOdds};
+/// let mut bob = Character::new("Bob");
+/// bob.reaction_modifiers.push(ReactionMod::new("Charisma", 1));
+/// let odds = ReactionOdds::for_character(&bob, false);
+/// assert_eq!(1, odds.modifier);
+/// // 9 or more on 3d6, since +1 gets the rest of the way to Neutral
+/// assert!((odds.at_least(ReactionLevel::Neutral) - 160.0 / 216.0).abs() < 1e-12);
+/// let total: f64 = ReactionLevel::ALL.iter().map(|level| odds.probability(*level)).sum();
+/// assert!((total - 1.0).abs() < 1e-12);
+///
+/// // even huge modifiers give odds that add up
+/// let odds = ReactionOdds::new(i32::MAX);
+/// assert_eq!(1.0, odds.probability(ReactionLevel::Excellent));
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ReactionOdds {
+	/// The modifier applied to the 3d6 roll.
+	pub modifier: i32,
+	/// The probability of each level, in the same order as ReactionLevel::ALL.
+	pub probabilities: [f64; 8],
+}//end struct ReactionOdds
+
+impl ReactionOdds {
+	/// Calculates the odds of each reaction level for a 3d6 roll with the modifier.
+	pub fn new(modifier: i32) -> ReactionOdds {
+		// past this, every total is already Disastrous or Excellent,
+		// and the totals stay far from the limits of i32
+		let distribution = Distribution::new(3, modifier.clamp(-ODDS_MODIFIER_LIMIT, ODDS_MODIFIER_LIMIT))
+			.expect("3 dice should be within the limit for a distribution");
+		let mut probabilities = [0.0; 8];
+		for (index, level) in ReactionLevel::ALL.iter().enumerate() {
+			probabilities[index] = distribution.probability_in(level.total_range());
+		}//end getting probability of each level
+		ReactionOdds {
+			modifier,
+			probabilities,
+		}//end struct construction
+	}//end new()
+
+	/// Calculates the odds of each reaction level for a character,
+	/// using their reaction_sum as the modifier.
+	pub fn for_character(character: &Character, count_disabled: bool) -> ReactionOdds {
+		ReactionOdds::new(character.reaction_sum(count_disabled))
+	}//end for_character()
+
+	/// The probability of getting exactly this reaction level.
+	pub fn probability(&self, level: ReactionLevel) -> f64 {
+		self.probabilities[level as usize]
+	}//end probability()
+
+	/// The probability of getting this reaction level or better.
+	pub fn at_least(&self, level: ReactionLevel) -> f64 {
+		ReactionLevel::ALL.iter()
+			.filter(|other| **other >= level)
+			.map(|other| self.probability(*other))
+			.sum()
+	}//end at_least()
+
+	/// The probability of getting this reaction level or worse.
+	pub fn at_most(&self, level: ReactionLevel) -> f64 {
+		ReactionLevel::ALL.iter()
+			.filter(|other| **other <= level)
+			.map(|other| self.probability(*other))
+			.sum()
+	}//end at_most()
+}//end impl for ReactionOdds
+
+/// The reasons that a reaction modifier might not be counted.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum SkipReason {
+	/// The modifier is turned off.
+	Disabled,
+	/// The character it belongs to isn't involved in the interaction.
+	NotInvolved,
+	/// The party rule didn't pick the character it belongs to.
+	NotChosenByRule,
+	/// The NPC isn't in the audience the modifier applies to.
+	WrongAudience,
+	/// The NPC failed to recognize the character with the Reputation.
+	NotRecognized,
+	/// The modifier only applies in other situations.
+	WrongSituation,
+}//end enum SkipReason
+
+impl std::fmt::Display for SkipReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SkipReason::Disabled => write!(f, "disabled"),
+			SkipReason::NotInvolved => write!(f, "not involved"),
+			SkipReason::NotChosenByRule => write!(f, "not chosen by party rule"),
+			SkipReason::WrongAudience => write!(f, "doesn't apply to this NPC"),
+			SkipReason::NotRecognized => write!(f, "not recognized"),
+			SkipReason::WrongSituation => write!(f, "doesn't apply in this situation"),
+		}//end matching self to text
+	}//end fmt()
+}//end impl Display for SkipReason
+
+/// Whether a modifier in a breakdown was counted, or why it wasn't.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub enum EntryStatus {
+	Counted,
+	Skipped(SkipReason),
+}//end enum EntryStatus
+
+/// The roll made to see whether an NPC recognizes a character's Reputation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct RecognitionCheck {
+	/// How often the Reputation is recognized.
+	pub recognition: Recognition,
+	/// The roll made against the recognition frequency.
+	pub roll: SuccessRoll,
+}//end struct RecognitionCheck
+
+impl RecognitionCheck {
+	/// Rolls for recognition using the session.  
+	/// Returns None if the recognition frequency doesn't need a roll.
+	pub fn roll(recognition: Recognition, session: &mut RollSession) -> Option<RecognitionCheck> {
+		let target = recognition.target()?;
+		Some(RecognitionCheck {
+			recognition,
+			roll: session.success_roll(target),
+		})//end struct construction
+	}//end roll()
+
+	/// Whether the NPC recognized the Reputation.
+	pub fn recognized(&self) -> bool { self.roll.succeeded() }
+}//end impl for RecognitionCheck
+
+impl std::fmt::Display for RecognitionCheck {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "recognition: rolled {} vs {}", self.roll.total(), self.recognition)
+	}//end fmt()
+}//end impl Display for RecognitionCheck
+
+/// One modifier in a reaction breakdown, along with where it came from.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct BreakdownEntry {
+	/// Where the modifier came from, such as the name of a character.
+	pub source: String,
+	/// The modifier itself.
+	pub modifier: ReactionMod,
+	/// Whether the modifier was counted.
+	pub status: EntryStatus,
+	/// The recognition roll, if the modifier is a Reputation that needed one.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub recognition: Option<RecognitionCheck>,
+}//end struct BreakdownEntry
+
+impl BreakdownEntry {
+	/// Whether this entry was counted towards the total.
+	pub fn is_counted(&self) -> bool { self.status == EntryStatus::Counted }
+}//end impl for BreakdownEntry
+
+impl std::fmt::Display for BreakdownEntry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let name = if self.source.is_empty() {self.modifier.name.clone()}
+			else {format!("{} ({})", self.modifier.name, self.source)};
+		match self.status {
+			EntryStatus::Counted => write!(f, "{:+} {}", self.modifier.modi, name)?,
+			EntryStatus::Skipped(reason) => write!(f, "    skipped {:+} {}: {}", self.modifier.modi, name, reason)?,
+		}//end matching whether this entry was counted
+		match &self.recognition {
+			Some(check) => write!(f, " [{}]", check),
+			None => Ok(()),
+		}//end matching whether there was a recognition roll
+	}//end fmt()
+}//end impl Display for BreakdownEntry
+
+/// An itemized explanation of a reaction modifier, listing every
+/// modifier that was looked at and whether it was counted.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Character, ReactionMod};
+/// let mut witch = Character::new("Witch");
+/// witch.reaction_modifiers.push(ReactionMod::new("Wicked", -2));
+/// witch.reaction_modifiers.push(ReactionMod {name: "Stigma".to_string(), modi: -1, enabled: false, ..Default::default()});
+/// let breakdown = witch.reaction_breakdown(false);
+/// assert_eq!(-2, breakdown.total);
+/// assert_eq!(1, breakdown.counted().count());
+/// assert_eq!(1, breakdown.skipped().count());
+/// assert_eq!(
+///     "-2 Wicked (Witch)\n    skipped -1 Stigma (Witch): disabled\nTotal: -2",
+///     breakdown.to_string()
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub struct ReactionBreakdown {
+	/// Every modifier considered, counted or not.
+	pub entries: Vec<BreakdownEntry>,
+	/// The final modifier.
+	pub total: i32,
+}//end struct ReactionBreakdown
+
+impl ReactionBreakdown {
+	/// Iterates over the entries that were counted.
+	pub fn counted(&self) -> impl Iterator<Item = &BreakdownEntry> {
+		self.entries.iter().filter(|entry| entry.is_counted())
+	}//end counted()
+
+	/// Iterates over the entries that were skipped.
+	pub fn skipped(&self) -> impl Iterator<Item = &BreakdownEntry> {
+		self.entries.iter().filter(|entry| !entry.is_counted())
+	}//end skipped()
+}//end impl for ReactionBreakdown
+
+impl std::fmt::Display for ReactionBreakdown {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for entry in self.entries.iter() {
+			writeln!(f, "{}", entry)?;
+		}//end writing each entry
+		write!(f, "Total: {:+}", self.total)
+	}//end fmt()
+}//end impl Display for ReactionBreakdown
+
+/// Why a modifier was skipped in an encounter, if it was,
+/// along with its recognition roll, if it had one.
+type Resolution = (Option<SkipReason>, Option<RecognitionCheck>);
+
+/// An encounter after the rolls shared by every situation have been made,
+/// with a Resolution for each modifier in the same order as the breakdowns.
+struct Resolved {
+	/// The party, with modifiers that don't apply turned off.
+	party: Party,
+	party_modifiers: Vec<Resolution>,
+	npc_modifiers: Vec<Resolution>,
+	situational: Vec<Resolution>,
+}//end struct Resolved
+
+/// The source given in breakdowns for situational modifiers.
+pub const SITUATION_SOURCE: &str = "Situation";
+
+/// The name given in breakdowns to the modifier worked out
+/// from the difference in Status between a character and the NPC.
+pub const STATUS_DIFFERENCE: &str = "Status Difference";
+
+/// Everything that goes into a single reaction roll: the party
+/// being reacted to, the NPC doing the reacting, and any modifiers
+/// from the situation itself.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::{Character, Npc, Party, PartyRule, ReactionMod, Recognition};
+/// # use gurps_reactions::audience::{parse_conditions, parse_tags};
+/// # use gurps_reactions::dice::RollSession;
+/// # use gurps_reactions::reaction::{Encounter, EntryStatus, SkipReason, STATUS_DIFFERENCE};
+/// let mut session = RollSession::seeded(1);
+/// let mut bob = Character::new("Bob");
+/// bob.reaction_modifiers.push(ReactionMod::new("Charisma", 2));
+/// let mut guard = Npc::new("Gate Guard");
+/// guard.reaction_modifiers.push(ReactionMod::new("Intolerance (Dwarves)", -3));
+/// let mut encounter = Encounter::new(Party::from_characters(vec![bob]), guard);
+/// encounter.situational.push(ReactionMod::new("Bribe", 1));
+///
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// assert_eq!(0, breakdown.total);
+/// assert_eq!(3, breakdown.counted().count());
+/// assert_eq!("Situation", breakdown.entries[2].source);
+///
+/// // modifiers for a particular audience only count for that audience
+/// let mut elf_friend = ReactionMod::new("Elf-Friend", 2);
+/// elf_friend.audience = parse_conditions("species=elf");
+/// encounter.party.members[0].character.reaction_modifiers.push(elf_friend);
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// assert_eq!(0, breakdown.total);
+/// assert_eq!(EntryStatus::Skipped(SkipReason::WrongAudience), breakdown.entries[1].status);
+/// encounter.npc.tags = parse_tags("species=elf");
+/// assert_eq!(2, encounter.breakdown(&PartyRule::Spokesperson, &mut session).total);
+///
+/// // the same goes for situational and NPC modifiers
+/// let mut dwarf_feast = ReactionMod::new("Dwarven Feast Day", 2);
+/// dwarf_feast.audience = parse_conditions("species=dwarf");
+/// encounter.situational.push(dwarf_feast);
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// assert_eq!(2, breakdown.total);
+/// assert_eq!(EntryStatus::Skipped(SkipReason::WrongAudience), breakdown.entries.last().unwrap().status);
+/// encounter.situational.pop();
+///
+/// // reputations also have to be recognized
+/// let infamous = ReactionMod::reputation("Orc-Slayer", -4, parse_conditions("species=orc"), Recognition::SevenOrLess);
+/// encounter.party.members[0].character.reaction_modifiers.push(infamous);
+/// encounter.npc.tags = parse_tags("species=orc");
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// let check = breakdown.entries[2].recognition.clone().unwrap();
+/// if check.recognized() {
+///     assert_eq!(-4, breakdown.total);
+/// } else {
+///     assert_eq!(0, breakdown.total);
+///     assert_eq!(EntryStatus::Skipped(SkipReason::NotRecognized), breakdown.entries[2].status);
+/// }
+///
+/// // reputations in the situation have to be recognized too
+/// encounter.situational.push(ReactionMod::reputation("Town Hero", 3, Vec::new(), Recognition::TenOrLess));
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// let entry = breakdown.entries.last().unwrap();
+/// assert_eq!(entry.recognition.as_ref().unwrap().recognized(), entry.is_counted());
+/// encounter.situational.pop();
+///
+/// // but nobody rolls to recognize characters who aren't involved
+/// encounter.party.members[0].involved = false;
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// assert_eq!(EntryStatus::Skipped(SkipReason::NotInvolved), breakdown.entries[2].status);
+/// assert_eq!(None, breakdown.entries[2].recognition);
+/// encounter.party.members[0].involved = true;
+///
+/// // differences in Status are worked out from both sides
+/// encounter.party.members[0].character.status = 2;
+/// encounter.npc.status = 1;
+/// let breakdown = encounter.breakdown(&PartyRule::Spokesperson, &mut session);
+/// let status_entry = breakdown.entries.iter().find(|entry| entry.modifier.name == STATUS_DIFFERENCE).unwrap();
+/// assert_eq!(1, status_entry.modifier.modi);
+/// assert_eq!("Bob", status_entry.source);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub struct Encounter {
+	/// The characters being reacted to.
+	pub party: Party,
+	/// The NPC doing the reacting.
+	pub npc: Npc,
+	/// Modifiers from the circumstances, rather than anyone involved.
+	pub situational: Vec<ReactionMod>,
+}//end struct Encounter
+
+impl Encounter {
+	/// Creates a new encounter with no situational modifiers.
+	pub fn new(party: Party, npc: Npc) -> Encounter {
+		Encounter {
+			party,
+			npc,
+			situational: Vec::new(),
+		}//end struct construction
+	}//end new()
+
+	/// Combines the party's modifiers, using the strategy, with the NPC's
+	/// modifiers and the situational modifiers into a single breakdown
+	/// for a general reaction.  
+	/// Disabled modifiers are never counted, and neither are
+	/// modifiers whose audience doesn't include the NPC.  
+	/// Reputations are rolled for recognition with the session.
+	pub fn breakdown<S: PartyStrategy + ?Sized>(&self, strategy: &S, session: &mut RollSession) -> ReactionBreakdown {
+		let resolved = self.resolve(session);
+		self.breakdown_in(strategy, Situation::General, &resolved)
+	}//end breakdown()
+
+	/// Gets a breakdown for each situation, in the same order as
+	/// Situation::ALL, leaving out modifiers that don't apply in
+	/// that situation.  
+	/// Reputations are only rolled for once, and that roll is shared
+	/// by every situation.
+	pub fn situation_breakdowns<S: PartyStrategy + ?Sized>(&self, strategy: &S, session: &mut RollSession) -> Vec<(Situation, ReactionBreakdown)> {
+		let resolved = self.resolve(session);
+		Situation::ALL.iter()
+			.map(|situation| (*situation, self.breakdown_in(strategy, *situation, &resolved)))
+			.collect()
+	}//end situation_breakdowns()
+
+	/// Rolls the reaction for this encounter, reading the same dice
+	/// against the modifiers of every situation.  
+	/// The modifier of the roll itself is the one for a general reaction.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::{Character, Npc, Party, PartyRule, ReactionMod};
+	/// # use gurps_reactions::dice::RollSession;
+	/// # use gurps_reactions::reaction::{Encounter, Situation};
+	/// let mut merchant = Character::new("Merchant");
+	/// let mut merchant_rank = ReactionMod::new("Merchant Rank", 3);
+	/// merchant_rank.situations.push(Situation::Commercial);
+	/// merchant.reaction_modifiers.push(merchant_rank);
+	/// let encounter = Encounter::new(Party::from_characters(vec![merchant]), Npc::new("Buyer"));
+	///
+	/// let result = encounter.roll(&PartyRule::Spokesperson, &mut RollSession::seeded(7));
+	/// assert_eq!(Situation::ALL.len(), result.reactions.len());
+	/// let general = result.reaction(Situation::General).unwrap();
+	/// let commercial = result.reaction(Situation::Commercial).unwrap();
+	/// assert_eq!(result.roll.total, general.total);
+	/// assert_eq!(general.total + 3, commercial.total);
+	/// ```
+	pub fn roll<S: PartyStrategy + ?Sized>(&self, strategy: &S, session: &mut RollSession) -> ReactionResult {
+		self.roll_keeping(strategy, session, 1, KeepRule::Highest)
+	}//end roll()
+
+	/// Rolls the reaction for this encounter several times and keeps
+	/// one of them, as with Luck or Cursed. The rest are kept in the
+	/// result as discarded rolls.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::{Character, Npc, Party, PartyRule};
+	/// # use gurps_reactions::dice::{KeepRule, RollSession};
+	/// # use gurps_reactions::reaction::Encounter;
+	/// let encounter = Encounter::new(Party::from_characters(vec![Character::new("Lucky")]), Npc::new("Guard"));
+	/// let result = encounter.roll_keeping(&PartyRule::Spokesperson, &mut RollSession::seeded(3), 3, KeepRule::Highest);
+	/// assert_eq!(2, result.discarded.len());
+	/// assert!(result.discarded.iter().all(|roll| roll.total <= result.roll.total));
+	/// ```
+	pub fn roll_keeping<S: PartyStrategy + ?Sized>(&self, strategy: &S, session: &mut RollSession, times: usize, keep: KeepRule) -> ReactionResult {
+		let breakdowns = self.situation_breakdowns(strategy, session);
+		let general_total = general_total(&breakdowns);
+		let rolls = session.roll_keeping(times, 3, general_total, keep);
+		ReactionResult::new(rolls, breakdowns)
+	}//end roll_keeping()
+
+	/// Works out the reaction for this encounter from dice that were
+	/// rolled by hand. The session is still used for other rolls,
+	/// such as recognizing Reputations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use gurps_reactions::character::{Character, Npc, Party, PartyRule, ReactionMod};
+	/// # use gurps_reactions::dice::{Roll, RollSession};
+	/// # use gurps_reactions::reaction::{Encounter, ReactionLevel};
+	/// let mut bard = Character::new("Bard");
+	/// bard.reaction_modifiers.push(ReactionMod::new("Voice", 2));
+	/// let encounter = Encounter::new(Party::from_characters(vec![bard]), Npc::new("Innkeeper"));
+	/// let dice = Roll::from_faces(&[5, 4, 3], 0).unwrap().dice;
+	/// let result = encounter.roll_from_dice(&PartyRule::Spokesperson, &mut RollSession::seeded(1), dice);
+	/// assert_eq!(14, result.roll.total);
+	/// assert_eq!(ReactionLevel::Good, result.level());
+	/// ```
+	pub fn roll_from_dice<S: PartyStrategy + ?Sized>(&self, strategy: &S, session: &mut RollSession, dice: Vec<Die>) -> ReactionResult {
+		let breakdowns = self.situation_breakdowns(strategy, session);
+		let general_total = general_total(&breakdowns);
+		let rolls = KeptRoll::new(vec![Roll::from_dice(dice, general_total)], KeepRule::Highest);
+		ReactionResult::new(rolls, breakdowns)
+	}//end roll_from_dice()
+
+	/// Builds the breakdown for one situation out of the party
+	/// after it's been resolved for this encounter.
+	fn breakdown_in<S: PartyStrategy + ?Sized>(&self, strategy: &S, situation: Situation, resolved: &Resolved) -> ReactionBreakdown {
+		let mut party = resolved.party.clone();
+		let mut reasons = Vec::new();
+		let party_modifiers = party.members.iter_mut()
+			.flat_map(|member| member.character.reaction_modifiers.iter_mut());
+		for (modi, (reason, _)) in party_modifiers.zip(resolved.party_modifiers.iter()) {
+			reasons.push(turn_off(modi, reason.or_else(|| situation_skip_reason(modi, situation))));
+		}//end checking each modifier of the party against the situation
+		let mut breakdown = party.reaction_breakdown(strategy, false);
+		attach_recognition(&mut breakdown.entries, &resolved.party_modifiers);
+		apply_skip_reasons(&mut breakdown.entries, reasons);
+
+		let mut npc = self.npc.clone();
+		let reasons: Vec<Option<SkipReason>> = npc.reaction_modifiers.iter_mut()
+			.zip(resolved.npc_modifiers.iter())
+			.map(|(modi, (reason, _))| turn_off(modi, reason.or_else(|| situation_skip_reason(modi, situation))))
+			.collect();
+		let mut npc_breakdown = npc.reaction_breakdown(false);
+		// the base attitude comes before the modifiers, if it's there
+		let offset = npc_breakdown.entries.len() - reasons.len();
+		attach_recognition(&mut npc_breakdown.entries[offset..], &resolved.npc_modifiers);
+		apply_skip_reasons(&mut npc_breakdown.entries[offset..], reasons);
+		breakdown.entries.extend(npc_breakdown.entries);
+		breakdown.total += npc_breakdown.total;
+
+		for (modi, (reason, check)) in self.situational.iter().zip(resolved.situational.iter()) {
+			let status = if !modi.enabled {EntryStatus::Skipped(SkipReason::Disabled)}
+				else if let Some(reason) = reason.or_else(|| situation_skip_reason(modi, situation)) {EntryStatus::Skipped(reason)}
+				else {EntryStatus::Counted};
+			if status == EntryStatus::Counted { breakdown.total += modi.modi; }
+			breakdown.entries.push(BreakdownEntry {
+				source: SITUATION_SOURCE.to_string(),
+				modifier: modi.clone(),
+				status,
+				recognition: check.clone(),
+			});
+		}//end adding each situational modifier
+		breakdown
+	}//end breakdown_in()
+
+	/// Works out which modifiers don't apply in this encounter and makes
+	/// any recognition rolls, so every situation can share them.  
+	/// The party gets every modifier that doesn't apply turned off,
+	/// and each member also gets a modifier for the difference between
+	/// their Status and the NPC's, if there is one.
+	fn resolve(&self, session: &mut RollSession) -> Resolved {
+		let mut party = self.party.clone();
+		let mut resolutions = Vec::new();
+		for member in party.members.iter_mut() {
+			let status_reaction = self.npc.status_reaction(member.character.status);
+			if status_reaction != 0 {
+				member.character.reaction_modifiers.push(ReactionMod::new(STATUS_DIFFERENCE, status_reaction));
+			}//end if the difference in Status matters
+			for modi in member.character.reaction_modifiers.iter_mut() {
+				// members who aren't involved don't get recognized, so they don't use up any rolls
+				let (reason, check) = if modi.enabled && member.involved {self.skip_reason(modi, session)} else {(None, None)};
+				resolutions.push((turn_off(modi, reason), check));
+			}//end checking each modifier of the member
+		}//end checking each member
+		let mut resolve_all = |modifiers: &[ReactionMod]| -> Vec<Resolution> {
+			modifiers.iter()
+				.map(|modi| if modi.enabled {self.skip_reason(modi, session)} else {(None, None)})
+				.collect()
+		};
+		let npc_modifiers = resolve_all(&self.npc.reaction_modifiers);
+		let situational = resolve_all(&self.situational);
+		Resolved {
+			party,
+			party_modifiers: resolutions,
+			npc_modifiers,
+			situational,
+		}//end struct construction
+	}//end resolve()
+
+	/// Gets the reason an enabled modifier doesn't
+	/// apply in this encounter, if there is one, along with the
+	/// recognition roll if the modifier is a Reputation.
+	fn skip_reason(&self, modi: &ReactionMod, session: &mut RollSession) -> Resolution {
+		if !modi.applies_to(&self.npc) { return (Some(SkipReason::WrongAudience), None); }
+		let check = modi.reputation.and_then(|recognition| RecognitionCheck::roll(recognition, session));
+		if check.as_ref().is_some_and(|check| !check.recognized()) { return (Some(SkipReason::NotRecognized), check); }
+		(None, check)
+	}//end skip_reason()
+}//end impl for Encounter
+
+/// Gets the total of the breakdown for a general reaction, or 0 if there isn't one.
+fn general_total(breakdowns: &[(Situation, ReactionBreakdown)]) -> i32 {
+	breakdowns.iter()
+		.find(|(situation, _)| *situation == Situation::General)
+		.map_or(0, |(_, breakdown)| breakdown.total)
+}//end general_total()
+
+/// Gets the reason an enabled modifier doesn't apply in the situation, if there is one.
+fn situation_skip_reason(modi: &ReactionMod, situation: Situation) -> Option<SkipReason> {
+	if modi.enabled && !modi.applies_in(situation) {Some(SkipReason::WrongSituation)}
+	else {None}
+}//end situation_skip_reason()
+
+/// Attaches the recognition rolls to the breakdown entries they were made for.
+fn attach_recognition(entries: &mut [BreakdownEntry], resolutions: &[Resolution]) {
+	for (entry, (_, check)) in entries.iter_mut().zip(resolutions) {
+		entry.recognition = check.clone();
+	}//end attaching recognition rolls to each entry
+}//end attach_recognition()
+
+/// Turns off the modifier if there's a reason to skip it, passing the reason along.
+fn turn_off(modi: &mut ReactionMod, reason: Option<SkipReason>) -> Option<SkipReason> {
+	if reason.is_some() { modi.enabled = false; }
+	reason
+}//end turn_off()
+
+/// Breakdowns only know modifiers turned off by an encounter as
+/// disabled, so this swaps in the real reason they were skipped.
+fn apply_skip_reasons(entries: &mut [BreakdownEntry], reasons: Vec<Option<SkipReason>>) {
+	for (entry, reason) in entries.iter_mut().zip(reasons) {
+		if let (Some(reason), EntryStatus::Skipped(SkipReason::Disabled)) = (reason, entry.status) {
+			entry.status = EntryStatus::Skipped(reason);
+			entry.modifier.enabled = true;
+		}//end if this modifier was turned off by the encounter
+	}//end fixing up each entry
+}//end apply_skip_reasons()
+
+/// How a single reaction roll turned out in one situation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct SituationReaction {
+	/// The situation this reaction is for.
+	pub situation: Situation,
+	/// The modifiers that applied in this situation.
+	pub breakdown: ReactionBreakdown,
+	/// The dice plus the modifiers for this situation.
+	pub total: i32,
+	/// The reaction level reached by the total.
+	pub level: ReactionLevel,
+}//end struct SituationReaction
+
+impl SituationReaction {
+	/// Reads the sum of the dice, plus the breakdown's total, as a reaction in the situation.
+	pub fn new(situation: Situation, breakdown: ReactionBreakdown, dice_sum: i32) -> SituationReaction {
+		let total = dice_sum + breakdown.total;
+		SituationReaction {
+			situation,
+			breakdown,
+			total,
+			level: ReactionLevel::from_total(total),
+		}//end struct construction
+	}//end new()
+
+	/// Gets what the NPC does in this situation.
+	pub fn outcome(&self) -> SituationOutcome { self.level.outcome(self.situation) }
+}//end impl for SituationReaction
+
+impl std::fmt::Display for SituationReaction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} ({}, {}): {}", self.situation, self.total, self.level, self.outcome().description)
+	}//end fmt()
+}//end impl Display for SituationReaction
+
+/// The result of rolling the reaction for an encounter, read against every situation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+pub struct ReactionResult {
+	/// The dice that were rolled, with the modifier for a general reaction.
+	pub roll: Roll,
+	/// The reaction in each situation, in the same order as Situation::ALL.
+	pub reactions: Vec<SituationReaction>,
+	/// Any rolls that were made but not kept, such as with Luck.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub discarded: Vec<Roll>,
+}//end struct ReactionResult
+
+impl ReactionResult {
+	/// Reads the kept roll against the breakdown for each situation.
+	pub fn new(rolls: KeptRoll, breakdowns: Vec<(Situation, ReactionBreakdown)>) -> ReactionResult {
+		let roll = rolls.kept().clone();
+		let reactions = breakdowns.into_iter()
+			.map(|(situation, breakdown)| SituationReaction::new(situation, breakdown, roll.dice_sum()))
+			.collect();
+		ReactionResult {
+			roll,
+			reactions,
+			discarded: rolls.discarded().cloned().collect(),
+		}//end struct construction
+	}//end new()
+
+	/// Gets the reaction for one situation.
+	pub fn reaction(&self, situation: Situation) -> Option<&SituationReaction> {
+		self.reactions.iter().find(|reaction| reaction.situation == situation)
+	}//end reaction()
+
+	/// Gets the reaction level of the roll itself, as a general reaction.
+	pub fn level(&self) -> ReactionLevel { ReactionLevel::from_total(self.roll.total) }
+}//end impl for ReactionResult
@@ -0,0 +1,216 @@
+use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+
+use crate::{character::{ReactionMod, Recognition}, reaction::Situation};
+
+/// The built-in trait catalog, as JSON.
+const CATALOG_JSON: &str = include_str!("traits.json");
+
+/// One level of a leveled trait, along with the reaction modifier it gives.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub struct TraitLevel {
+	/// The level number, as written on a character sheet.
+	pub level: i32,
+	/// The name of this level, like "Attractive".
+	pub name: String,
+	/// The reaction modifier at this level.
+	pub modifier: i32,
+}//end struct TraitLevel
+
+impl TraitLevel {
+	/// Creates a new trait level.
+	pub fn new(level: i32, name: &str, modifier: i32) -> TraitLevel {
+		TraitLevel {
+			level,
+			name: name.to_string(),
+			modifier,
+		}//end struct construction
+	}//end new()
+}//end impl for TraitLevel
+
+/// A shorthand for a level table where every level
+/// is worth the same reaction modifier.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub struct PerLevel {
+	/// The lowest level.
+	pub min: i32,
+	/// The highest level.
+	pub max: i32,
+	/// The reaction modifier for each level.
+	pub modifier: i32,
+}//end struct PerLevel
+
+/// An advantage or disadvantage that affects reactions, as found in the catalog.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::character::Recognition;
+/// # use gurps_reactions::traits::find_trait;
+/// let appearance = find_trait("appearance").unwrap();
+/// assert!(appearance.is_leveled());
+/// assert_eq!(Some(-2), appearance.modifier(-2));
+/// assert_eq!("Ugly", appearance.level(-2).unwrap().name);
+/// assert_eq!(None, appearance.modifier(9));
+/// assert_eq!(3, appearance.clamp_level(9));
+///
+/// let charisma = find_trait("Charisma").unwrap();
+/// assert_eq!(Some(3), charisma.modifier(3));
+/// assert_eq!("B41", charisma.page);
+///
+/// let voice = find_trait("Voice").unwrap();
+/// assert!(!voice.is_leveled());
+/// assert_eq!(2, voice.to_modifier(voice.default).modi);
+/// assert_eq!(None, voice.to_modifier(voice.default).reputation);
+///
+/// let reputation = find_trait("Reputation").unwrap();
+/// assert_eq!(Some(Recognition::Always), reputation.to_modifier(2).reputation);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default, Serialize, Deserialize)]
+pub struct TraitInfo {
+	/// The name of the trait, like "Charisma".
+	pub name: String,
+	/// The usual reaction modifier, or for leveled traits the usual level.
+	#[serde(default)]
+	pub default: i32,
+	/// Every level the trait can have, from lowest to highest.
+	/// If empty, the trait isn't leveled.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub levels: Vec<TraitLevel>,
+	/// If given, the levels are filled in from this when the catalog is loaded.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub per_level: Option<PerLevel>,
+	/// The situations the trait applies in. If empty, it applies in all of them.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub situations: Vec<Situation>,
+	/// Where to find the trait, like "B41".
+	#[serde(default)]
+	pub page: String,
+	/// A short summary of how the trait affects reactions.
+	#[serde(default)]
+	pub rules: String,
+	/// Whether the trait is a Reputation, which only counts
+	/// when the NPC recognizes the character.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub reputation: bool,
+}//end struct TraitInfo
+
+impl TraitInfo {
+	/// Creates a new leveled trait out of a table of levels.
+	pub fn new(name: &str, levels: Vec<TraitLevel>) -> TraitInfo {
+		TraitInfo {
+			name: name.to_string(),
+			levels,
+			..Default::default()
+		}//end struct construction
+	}//end new()
+
+	/// Creates a leveled trait where each level is worth the
+	/// same reaction modifier, from min to max levels.
+	pub fn per_level(name: &str, min: i32, max: i32, per_level: i32) -> TraitInfo {
+		let mut info = TraitInfo {
+			name: name.to_string(),
+			per_level: Some(PerLevel { min, max, modifier: per_level }),
+			..Default::default()
+		};
+		info.fill_levels();
+		info
+	}//end per_level()
+
+	/// Fills in the levels from per_level, if it's given.
+	fn fill_levels(&mut self) {
+		if let Some(per_level) = self.per_level {
+			self.levels = (per_level.min..=per_level.max)
+				.map(|level| TraitLevel::new(level, &format!("{} {}", self.name, level), level * per_level.modifier))
+				.collect();
+		}//end if there's a per level shorthand
+	}//end fill_levels()
+
+	/// Whether this trait comes in levels.
+	pub fn is_leveled(&self) -> bool { !self.levels.is_empty() }
+
+	/// Gets one level of the trait, if it has that level.
+	pub fn level(&self, level: i32) -> Option<&TraitLevel> {
+		self.levels.iter().find(|trait_level| trait_level.level == level)
+	}//end level()
+
+	/// Gets the reaction modifier for one level of the trait.
+	pub fn modifier(&self, level: i32) -> Option<i32> {
+		self.level(level).map(|trait_level| trait_level.modifier)
+	}//end modifier()
+
+	/// The lowest level this trait can have.
+	pub fn min_level(&self) -> i32 {
+		self.levels.iter().map(|trait_level| trait_level.level).min().unwrap_or(0)
+	}//end min_level()
+
+	/// The highest level this trait can have.
+	pub fn max_level(&self) -> i32 {
+		self.levels.iter().map(|trait_level| trait_level.level).max().unwrap_or(0)
+	}//end max_level()
+
+	/// Moves the level into the range this trait allows.
+	pub fn clamp_level(&self, level: i32) -> i32 {
+		level.clamp(self.min_level(), self.max_level())
+	}//end clamp_level()
+
+	/// Creates a reaction modifier for this trait.
+	/// For leveled traits, value is the level, and otherwise it's the modifier.
+	/// Reputations start out always recognized, with no affected group.
+	pub fn to_modifier(&self, value: i32) -> ReactionMod {
+		let mut modifier = if self.is_leveled() {ReactionMod::leveled(self, value)}
+			else if self.reputation {ReactionMod::reputation(&self.name, value, Vec::new(), Recognition::Always)}
+			else {ReactionMod::new(&self.name, value)};
+		modifier.situations = self.situations.clone();
+		modifier
+	}//end to_modifier()
+}//end impl for TraitInfo
+
+impl std::fmt::Display for TraitInfo {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} ({}): {}", self.name, self.page, self.rules)
+	}//end fmt()
+}//end impl Display for TraitInfo
+
+/// Gets the built-in catalog of traits that affect reactions.
+pub fn catalog() -> &'static [TraitInfo] {
+	static CATALOG: OnceLock<Vec<TraitInfo>> = OnceLock::new();
+	CATALOG.get_or_init(|| {
+		let mut catalog: Vec<TraitInfo> = serde_json::from_str(CATALOG_JSON)
+			.expect("the built-in trait catalog should be valid");
+		catalog.iter_mut().for_each(TraitInfo::fill_levels);
+		catalog
+	})
+}//end catalog()
+
+/// Finds a trait in the catalog by name, ignoring case.
+pub fn find_trait(name: &str) -> Option<&'static TraitInfo> {
+	let name = name.trim();
+	catalog().iter().find(|info| info.name.eq_ignore_ascii_case(name))
+}//end find_trait()
+
+/// Finds a leveled trait in the catalog by name, ignoring case.
+pub fn find_leveled_trait(name: &str) -> Option<&'static TraitInfo> {
+	find_trait(name).filter(|info| info.is_leveled())
+}//end find_leveled_trait()
+
+/// Searches the catalog for traits whose names contain the query,
+/// ignoring case. Names that start with the query come first.
+///
+/// # Examples
+///
+/// ```
+/// # use gurps_reactions::traits::search;
+/// let names: Vec<&str> = search("so").iter().map(|info| info.name.as_str()).collect();
+/// assert_eq!(vec!["Social Regard", "Social Stigma", "Odious Personal Habit"], names);
+/// assert!(search("").is_empty());
+/// ```
+pub fn search(query: &str) -> Vec<&'static TraitInfo> {
+	let query = query.trim().to_lowercase();
+	if query.is_empty() { return Vec::new(); }
+	let mut matches: Vec<&TraitInfo> = catalog().iter()
+		.filter(|info| info.name.to_lowercase().contains(&query))
+		.collect();
+	matches.sort_by_key(|info| !info.name.to_lowercase().starts_with(&query));
+	matches
+}//end search()